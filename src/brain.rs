@@ -0,0 +1,124 @@
+use std::collections::{HashMap, VecDeque};
+use std::fmt::Display;
+
+use crate::{Direction, Game, Position};
+
+/// Decides how the Minotaurs chase Theseus. `Game` asks its brain where each Minotaur steps, one
+/// Minotaur and one step at a time. Brains have to be `Send` and `Sync` so games can still be
+/// passed between threads.
+pub trait MinotaurBrain: Send + Sync {
+    /// The name the brain is chosen by, which is also written to save files
+    fn name(&self) -> &str;
+
+    /// Returns which way the Minotaur at `minotaur` steps, or None if it stays where it is. A
+    /// step into a wall is treated as staying put.
+    fn choose(&self, game: &Game, minotaur: Position) -> Option<Direction>;
+}
+
+/// The Minotaur brains that come with the game
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum Brain {
+    /// Steps closer horizontally if it can, and only otherwise vertically, like the original
+    /// puzzle
+    #[default]
+    Classic,
+    /// Steps closer vertically if it can, and only otherwise horizontally, like the red mummies
+    /// in Mummy Maze
+    VerticalFirst,
+    /// Steps closer along whichever of the rows or columns it's furthest from Theseus in
+    Greedy,
+    /// Follows the shortest path to Theseus, even if it leads away from him at first
+    Hunter,
+}
+
+impl Brain {
+    pub const ALL: [Brain; 4] = [
+        Brain::Classic,
+        Brain::VerticalFirst,
+        Brain::Greedy,
+        Brain::Hunter,
+    ];
+
+    /// Looks up a brain by the name used on the command line and in level packs
+    pub fn by_name(name: &str) -> Option<Brain> {
+        Brain::ALL.into_iter().find(|brain| brain.name() == name)
+    }
+}
+
+impl MinotaurBrain for Brain {
+    fn name(&self) -> &str {
+        match self {
+            Brain::Classic => "classic",
+            Brain::VerticalFirst => "vertical",
+            Brain::Greedy => "greedy",
+            Brain::Hunter => "hunter",
+        }
+    }
+
+    fn choose(&self, game: &Game, minotaur: Position) -> Option<Direction> {
+        match self {
+            Brain::Classic => first_closer(game, minotaur, true),
+            Brain::VerticalFirst => first_closer(game, minotaur, false),
+            Brain::Greedy => {
+                let theseus = game.theseus_pos();
+                let across = minotaur.col.abs_diff(theseus.col);
+                let down = minotaur.row.abs_diff(theseus.row);
+                first_closer(game, minotaur, across >= down)
+            }
+            Brain::Hunter => hunt(game, minotaur),
+        }
+    }
+}
+
+impl Display for Brain {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.name())
+    }
+}
+
+// returns the first open step that puts the Minotaur closer to Theseus, trying horizontal steps
+// before vertical ones or the other way around
+fn first_closer(game: &Game, pos: Position, horizontal_first: bool) -> Option<Direction> {
+    let theseus = game.theseus_pos();
+    let horizontal = [
+        (pos.col < theseus.col, Direction::Right),
+        (pos.col > theseus.col, Direction::Left),
+    ];
+    let vertical = [
+        (pos.row < theseus.row, Direction::Down),
+        (pos.row > theseus.row, Direction::Up),
+    ];
+    let moves = match horizontal_first {
+        true => [horizontal, vertical],
+        false => [vertical, horizontal],
+    };
+    // if no moves which put it closer are possible, it does nothing
+    moves
+        .into_iter()
+        .flatten()
+        .find(|&(closer, direction)| closer && game.minotaur_step(pos, direction).is_some())
+        .map(|(_, direction)| direction)
+}
+
+// returns the first step along the shortest path to Theseus, or None if there's no path
+fn hunt(game: &Game, pos: Position) -> Option<Direction> {
+    let theseus = game.theseus_pos();
+    // maps each cell reached to the first step taken on the way there
+    let mut first_steps: HashMap<Position, Option<Direction>> = HashMap::from([(pos, None)]);
+    let mut queue = VecDeque::from([pos]);
+    while let Some(current) = queue.pop_front() {
+        if current == theseus {
+            return first_steps[&current];
+        }
+        for direction in Direction::ALL {
+            let Some(next) = game.minotaur_step(current, direction) else {
+                continue;
+            };
+            if !first_steps.contains_key(&next) {
+                first_steps.insert(next, first_steps[&current].or(Some(direction)));
+                queue.push_back(next);
+            }
+        }
+    }
+    None
+}
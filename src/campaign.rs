@@ -0,0 +1,101 @@
+use std::error::Error;
+use std::fmt::Display;
+
+use crate::Level;
+
+/// Returned by `Progress::parse` for a line it can't read, counting from 1
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct InvalidProgress {
+    pub line: usize,
+}
+impl Display for InvalidProgress {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "Invalid progress on line {}", self.line)
+    }
+}
+impl Error for InvalidProgress {}
+
+/// A player's progress through a level pack: which levels are completed and the fewest moves
+/// each was won in. Levels are identified by title, so progress survives reordering the pack.
+///
+/// Progress is saved as one line per completed level, with the best move count then the title:
+/// ```text
+/// 10 The Corridor
+/// 27 Down the Chimney
+/// ```
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct Progress {
+    best: Vec<(String, usize)>,
+}
+
+impl Progress {
+    pub fn new() -> Progress {
+        Progress::default()
+    }
+
+    pub fn parse(text: &str) -> Result<Progress, InvalidProgress> {
+        let mut progress = Progress::new();
+        for (i, line) in text.lines().enumerate() {
+            if line.trim().is_empty() {
+                continue;
+            }
+            let parsed = line
+                .split_once(' ')
+                .and_then(|(moves, title)| Some((moves.parse().ok()?, title)));
+            match parsed {
+                Some((moves, title)) => progress.record_win(title, moves),
+                None => return Err(InvalidProgress { line: i + 1 }),
+            };
+        }
+        Ok(progress)
+    }
+
+    /// Returns the fewest moves the level has been won in, or None if it hasn't been won
+    pub fn best(&self, title: &str) -> Option<usize> {
+        self.best
+            .iter()
+            .find(|(completed, _)| completed == title)
+            .map(|&(_, moves)| moves)
+    }
+
+    /// Marks the level as completed, returning true if `moves` is a new best
+    pub fn record_win(&mut self, title: &str, moves: usize) -> bool {
+        match self
+            .best
+            .iter_mut()
+            .find(|(completed, _)| completed == title)
+        {
+            Some((_, best)) if *best <= moves => false,
+            Some((_, best)) => {
+                *best = moves;
+                true
+            }
+            None => {
+                self.best.push((title.to_string(), moves));
+                true
+            }
+        }
+    }
+
+    /// Returns the index of the first level that hasn't been completed, which is where the
+    /// campaign resumes, or None if every level is done
+    pub fn next_level(&self, levels: &[Level]) -> Option<usize> {
+        levels
+            .iter()
+            .position(|level| self.best(&level.title).is_none())
+    }
+
+    /// Returns true if the level at `index` can be played: every level before it is completed
+    pub fn is_unlocked(&self, levels: &[Level], index: usize) -> bool {
+        self.next_level(levels).is_none_or(|next| index <= next)
+    }
+}
+
+impl Display for Progress {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        for (title, moves) in &self.best {
+            writeln!(f, "{} {}", moves, title)?;
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,363 @@
+use std::collections::{HashSet, VecDeque};
+use std::fmt::Display;
+
+use crate::{
+    diagnostic, pack, thin, BoardError, Brain, Direction, Layout, Location, PackError, Pieces,
+    Position,
+};
+
+/// Something wrong with a board, as found by `check_board`
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Problem {
+    /// An error that keeps the board from being played at all
+    Board(BoardError),
+    /// A row of a board, counting from 1, that's a different length from the first row
+    RaggedRow {
+        line: usize,
+        length: usize,
+        expected: usize,
+    },
+    /// An open cell on the edge of the board, or a missing wall around a thin-wall board
+    OpenBoundary(Location),
+    /// Theseus has no path to the goal at `goal` or any exit, even with the Minotaurs out of the
+    /// way
+    GoalUnreachable { goal: Location, theseus: Location },
+    /// Theseus, at the given location, has no path to any exit on a board without a goal
+    ExitUnreachable(Location),
+    /// A Minotaur walled off from Theseus, so it can never catch him
+    MinotaurUnreachable(Location),
+    /// There's a path to the goal, but no way to get there without being caught
+    Unsolvable,
+}
+
+impl Problem {
+    // moves the locations down by `lines`, for a board that starts partway through a file
+    pub(crate) fn offset_lines(self, lines: usize) -> Problem {
+        let shift = |at: Location| Location {
+            line: at.line + lines,
+            ..at
+        };
+        match self {
+            Problem::Board(err) => Problem::Board(err.offset_lines(lines)),
+            Problem::RaggedRow {
+                line,
+                length,
+                expected,
+            } => Problem::RaggedRow {
+                line: line + lines,
+                length,
+                expected,
+            },
+            Problem::OpenBoundary(at) => Problem::OpenBoundary(shift(at)),
+            Problem::GoalUnreachable { goal, theseus } => Problem::GoalUnreachable {
+                goal: shift(goal),
+                theseus: shift(theseus),
+            },
+            Problem::ExitUnreachable(at) => Problem::ExitUnreachable(shift(at)),
+            Problem::MinotaurUnreachable(at) => Problem::MinotaurUnreachable(shift(at)),
+            Problem::Unsolvable => Problem::Unsolvable,
+        }
+    }
+
+    /// Returns where in the board the problem is, if it's at one place
+    pub fn location(&self) -> Option<Location> {
+        match *self {
+            Problem::Board(err) => err.location(),
+            Problem::RaggedRow {
+                line,
+                length,
+                expected,
+            } => Some(Location {
+                line,
+                column: length.min(expected) + 1,
+            }),
+            Problem::OpenBoundary(at)
+            | Problem::MinotaurUnreachable(at)
+            | Problem::ExitUnreachable(at) => Some(at),
+            Problem::GoalUnreachable { goal, .. } => Some(goal),
+            Problem::Unsolvable => None,
+        }
+    }
+
+    /// Describes the problem like `BoardError::render`, quoting the lines of `board` it's on
+    pub fn render(&self, path: &str, board: &str) -> String {
+        match *self {
+            Problem::Board(err) => err.render(path, board),
+            Problem::RaggedRow {
+                length, expected, ..
+            } => {
+                let label = format!(
+                    "{} characters long, but the first row is {}",
+                    length, expected
+                );
+                let at = self.location().expect("ragged rows have a location");
+                diagnostic::render(path, board, "ragged row", &[(at, '^', &label)])
+            }
+            Problem::OpenBoundary(at) => diagnostic::render(
+                path,
+                board,
+                "board isn't enclosed by walls",
+                &[(at, '^', "open to the outside")],
+            ),
+            Problem::GoalUnreachable { goal, theseus } => {
+                let mut notes = [
+                    (theseus, '-', "Theseus starts here"),
+                    (goal, '^', "no path from Theseus"),
+                ];
+                notes.sort_by_key(|&(at, _, _)| (at.line, at.column));
+                diagnostic::render(path, board, "the goal can't be reached", &notes)
+            }
+            Problem::ExitUnreachable(at) => diagnostic::render(
+                path,
+                board,
+                "no exit can be reached",
+                &[(at, '^', "no path to an exit from Theseus")],
+            ),
+            Problem::MinotaurUnreachable(at) => diagnostic::render(
+                path,
+                board,
+                "a Minotaur can't reach Theseus",
+                &[(at, '^', "walled off from Theseus")],
+            ),
+            Problem::Unsolvable => {
+                diagnostic::render(path, board, "the board can't be solved", &[])
+            }
+        }
+    }
+}
+
+impl Display for Problem {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Problem::Board(err) => write!(f, "{}", err),
+            Problem::RaggedRow {
+                line,
+                length,
+                expected,
+            } => write!(
+                f,
+                "Row on line {} is {} characters long, but the first row is {}",
+                line, length, expected
+            ),
+            Problem::OpenBoundary(at) => write!(f, "Board open to the outside at {}", at),
+            Problem::GoalUnreachable { goal, .. } => {
+                write!(f, "Goal at {} can't be reached from Theseus", goal)
+            }
+            Problem::ExitUnreachable(at) => {
+                write!(f, "No exit can be reached from Theseus at {}", at)
+            }
+            Problem::MinotaurUnreachable(at) => {
+                write!(f, "Minotaur at {} can't reach Theseus", at)
+            }
+            Problem::Unsolvable => write!(f, "Board can't be solved"),
+        }
+    }
+}
+
+/// Checks a board in either format for every problem it has, rather than stopping at the first
+/// like `Game::parse`. Returns an empty list if there are none.
+///
+/// Besides the errors that keep a board from being parsed, this finds rows of uneven length,
+/// gaps in the walls that Theseus could walk out through, pieces that are walled off from each
+/// other and boards that can't be won. Those last ones are only looked for once every piece is
+/// on the board. The goal is allowed to sit in a gap in the outer wall, as are marked exits.
+pub fn check_board(board: &str) -> Vec<Problem> {
+    check(board, Brain::Classic)
+}
+
+/// Checks every level of a level pack like `check_board`, returning the title of each level
+/// along with its problems. Their locations are lines of the whole pack, like the errors from
+/// `parse_pack`. Whether a level can be won is checked against the Minotaurs' brain from its
+/// `@brain` header. Fails like `parse_pack` if the headers can't be read.
+pub fn check_pack(text: &str) -> Result<Vec<(String, Vec<Problem>)>, PackError> {
+    let drafts = pack::read_drafts(text)?;
+    let levels = drafts.into_iter().map(|draft| {
+        let (board, first_line) = draft.board();
+        let problems = check(&board, draft.brain)
+            .into_iter()
+            .map(|problem| problem.offset_lines(first_line))
+            .collect();
+        (draft.title, problems)
+    });
+    Ok(levels.collect())
+}
+
+// checks a board whose Minotaurs move with `brain`
+fn check(board: &str, brain: Brain) -> Vec<Problem> {
+    let mut problems = Vec::new();
+    let (layout, pieces) = match parse_all(board, &mut problems) {
+        Some(parsed) => parsed,
+        None => return problems,
+    };
+    if let Layout::Cells(grid) = &layout {
+        let rows: Vec<usize> = grid.board.iter().map(|row| row.len()).collect();
+        let expected = rows.first().copied().unwrap_or(0);
+        for (y, &length) in rows.iter().enumerate() {
+            if length != expected {
+                problems.push(Problem::RaggedRow {
+                    line: y + 1,
+                    length,
+                    expected,
+                });
+            }
+        }
+    }
+
+    let missing = [
+        (pieces.theseus.is_none(), BoardError::NoTheseus),
+        (pieces.minotaurs.is_empty(), BoardError::NoMinotaur),
+        (
+            pieces.goal.is_none() && !layout.has_exits(),
+            BoardError::NoGoal,
+        ),
+    ];
+    for (missing, err) in missing {
+        if missing {
+            problems.push(Problem::Board(err));
+        }
+    }
+    let Ok(mut game) = pieces.into_game(layout) else {
+        return problems;
+    };
+    game.set_brain(brain);
+
+    // flood out from Theseus, ignoring the Minotaurs, to find everywhere he could ever be. He
+    // stops once he reaches the goal, so it can sit in a gap in the outer wall.
+    let mut reachable = HashSet::from([game.theseus]);
+    let mut queue = VecDeque::from([game.theseus]);
+    while let Some(pos) = queue.pop_front() {
+        if Some(pos) == game.goal {
+            continue;
+        }
+        for direction in Direction::ALL {
+            if let Some(next) = game.step(pos, direction) {
+                if reachable.insert(next) {
+                    queue.push_back(next);
+                }
+            }
+        }
+    }
+    let mut open: Vec<Location> = reachable
+        .iter()
+        .filter(|&&pos| Some(pos) != game.goal)
+        .flat_map(|&pos| open_sides(&game.layout, pos))
+        .collect();
+    open.sort_by_key(|at| (at.line, at.column));
+    problems.extend(open.into_iter().map(Problem::OpenBoundary));
+    for &minotaur in &game.minotaurs {
+        if !reachable.contains(&minotaur) {
+            problems.push(Problem::MinotaurUnreachable(location(
+                &game.layout,
+                minotaur,
+            )));
+        }
+    }
+    let escapable = reachable.iter().any(|&pos| {
+        Direction::ALL
+            .into_iter()
+            .any(|direction| game.layout.exits_through(pos, direction))
+    });
+    let theseus = location(&game.layout, game.theseus);
+    let unreachable = match game.goal {
+        _ if escapable => None,
+        Some(goal) if !reachable.contains(&goal) => Some(Problem::GoalUnreachable {
+            goal: location(&game.layout, goal),
+            theseus,
+        }),
+        Some(_) => None,
+        None => Some(Problem::ExitUnreachable(theseus)),
+    };
+    match unreachable {
+        Some(problem) => problems.push(problem),
+        None if game.solve().is_err() => problems.push(Problem::Unsolvable),
+        None => {}
+    }
+    problems
+}
+
+// parses the board, noting each error and blanking out the character that caused it so parsing
+// can carry on past it. Returns None if the board can't be parsed at all.
+fn parse_all(board: &str, problems: &mut Vec<Problem>) -> Option<(Layout, Pieces)> {
+    let mut board = board.to_string();
+    loop {
+        match Layout::parse(&board) {
+            Ok(parsed) => return Some(parsed),
+            Err(err) => {
+                problems.push(Problem::Board(err));
+                board = blank_out(&board, err.location()?);
+            }
+        }
+    }
+}
+
+// replaces the character at `at` with a space
+fn blank_out(board: &str, at: Location) -> String {
+    board
+        .lines()
+        .enumerate()
+        .map(|(y, line)| {
+            let mut line: String = if y + 1 == at.line {
+                line.chars()
+                    .enumerate()
+                    .map(|(x, c)| if x + 1 == at.column { ' ' } else { c })
+                    .collect()
+            } else {
+                line.to_string()
+            };
+            line.push('\n');
+            line
+        })
+        .collect()
+}
+
+// the location in the text of the board of the cell at `pos`
+fn location(layout: &Layout, pos: Position) -> Location {
+    match layout {
+        Layout::Cells(_) => Location::of(pos),
+        Layout::Edges(_) => Location::of(thin::expanded(pos)),
+    }
+}
+
+// finds where a piece at `pos` could walk off the edge of the board
+fn open_sides(layout: &Layout, pos: Position) -> Vec<Location> {
+    let mut open = Vec::new();
+    match layout {
+        Layout::Cells(grid) => {
+            // an open cell is on the edge if any step from it leaves the grid
+            let edge = Direction::ALL
+                .into_iter()
+                .any(|direction| match pos.step(direction) {
+                    Some(next) => grid.get(next).is_none(),
+                    None => true,
+                });
+            if edge {
+                open.push(Location::of(pos));
+            }
+        }
+        Layout::Edges(edges) => {
+            let walls = edges
+                .walls(pos.row, pos.col)
+                .expect("piece is on the board");
+            let sides = [
+                (pos.row == 0 && !walls.north, Direction::Up),
+                (pos.col == 0 && !walls.west, Direction::Left),
+                (
+                    pos.col + 1 == edges.width() && !walls.east,
+                    Direction::Right,
+                ),
+                (
+                    pos.row + 1 == edges.height() && !walls.south,
+                    Direction::Down,
+                ),
+            ];
+            for (missing, direction) in sides {
+                // exits are meant to be gaps in the wall
+                if missing && !layout.exits_through(pos, direction) {
+                    let gap = thin::expanded(pos).step(direction);
+                    open.extend(gap.map(Location::of));
+                }
+            }
+        }
+    }
+    open
+}
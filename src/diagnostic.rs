@@ -0,0 +1,102 @@
+use crate::{BoardError, Location};
+
+impl BoardError {
+    /// Describes the error like a compiler would, quoting the lines of `board` it's on with a
+    /// caret under the problem. `path` is only used to label the message.
+    /// ```text
+    /// error: more than one Theseus
+    ///  --> board.txt:4:5
+    ///   |
+    /// 2 | X T   X
+    ///   |   - first Theseus here
+    /// 4 | X   TGX
+    ///   |     ^ another Theseus
+    /// ```
+    pub fn render(&self, path: &str, board: &str) -> String {
+        let (summary, notes) = match *self {
+            BoardError::InvalidCharacter(c, at) => (
+                format!("invalid character {:?}", c),
+                vec![(at, '^', "not allowed here")],
+            ),
+            BoardError::InvalidSize => (String::from("invalid size"), vec![]),
+            BoardError::NoMinotaur => (String::from("no Minotaur on the board"), vec![]),
+            BoardError::NoTheseus => (String::from("no Theseus on the board"), vec![]),
+            BoardError::NoGoal => (String::from("no goal or exit on the board"), vec![]),
+            BoardError::MultipleTheseus { at, first } => (
+                String::from("more than one Theseus"),
+                vec![
+                    (first, '-', "first Theseus here"),
+                    (at, '^', "another Theseus"),
+                ],
+            ),
+            BoardError::MultipleGoal { at, first } => (
+                String::from("more than one goal"),
+                vec![(first, '-', "first goal here"), (at, '^', "another goal")],
+            ),
+            BoardError::ExitNotOnEdge(at) => (
+                String::from("exit must be in the outer wall"),
+                vec![(at, '^', "inside the board")],
+            ),
+        };
+
+        render(path, board, &summary, &notes)
+    }
+}
+
+// writes out an error message followed by each of `notes`: a location in `board`, the character
+// to mark it with and a label. Notes go in the order they appear in the board.
+pub(crate) fn render(
+    path: &str,
+    board: &str,
+    summary: &str,
+    notes: &[(Location, char, &str)],
+) -> String {
+    let mut text = format!("error: {}\n", summary);
+    let Some(&(at, _, _)) = notes.last() else {
+        text.push_str(&format!(" --> {}\n", path));
+        return text;
+    };
+    let gutter = notes
+        .iter()
+        .map(|(at, _, _)| digits(at.line))
+        .max()
+        .unwrap_or(1);
+    let blank = " ".repeat(gutter);
+    text.push_str(&format!(
+        "{}--> {}:{}:{}\n",
+        blank, path, at.line, at.column
+    ));
+    text.push_str(&format!("{} |\n", blank));
+    let lines: Vec<&str> = board.lines().collect();
+    let mut quoted = None;
+    for &(at, marker, label) in notes {
+        let line = lines.get(at.line - 1).copied().unwrap_or("");
+        // notes on the same line share one quote of it
+        if quoted != Some(at.line) {
+            text.push_str(&format!("{:>gutter$} | {}\n", at.line, line));
+            quoted = Some(at.line);
+        }
+        text.push_str(&format!(
+            "{} | {}{} {}\n",
+            blank,
+            indent(line, at),
+            marker,
+            label
+        ));
+    }
+    text
+}
+
+fn digits(n: usize) -> usize {
+    n.to_string().len()
+}
+
+// the whitespace that lines up with the character at `at`, keeping any tabs so the caret still
+// lines up when the terminal expands them
+fn indent(line: &str, at: Location) -> String {
+    line.chars()
+        .chain(std::iter::repeat(' '))
+        .take(at.column - 1)
+        .map(|c| if c == '\t' { '\t' } else { ' ' })
+        .collect()
+}
@@ -0,0 +1,171 @@
+use std::error::Error;
+use std::fmt::Display;
+
+use crate::Game;
+
+// how many random mazes are tried before giving up on finding a suitable one
+const MAX_ATTEMPTS: usize = 500;
+
+/// Settings for `generate`. `width` and `height` are the size of the board text, including the
+/// outer walls, and can be odd or even as long as they're at least 5.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct GeneratorOptions {
+    pub width: usize,
+    pub height: usize,
+    pub seed: u64,
+    /// The shortest solution of a generated board is at least this many moves long
+    pub min_moves: usize,
+}
+
+impl GeneratorOptions {
+    pub fn new(width: usize, height: usize) -> GeneratorOptions {
+        GeneratorOptions {
+            width,
+            height,
+            seed: 0,
+            min_moves: 1,
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum GenerateError {
+    TooSmall,
+    NoSolvableBoard,
+}
+impl Display for GenerateError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            GenerateError::TooSmall => write!(f, "Board must be at least 5 by 5"),
+            GenerateError::NoSolvableBoard => {
+                write!(f, "Could not generate a board with a long enough solution")
+            }
+        }
+    }
+}
+impl Error for GenerateError {}
+
+// small xorshift generator, so the same seed always gives the same board
+struct Rng(u64);
+
+impl Rng {
+    fn new(seed: u64) -> Rng {
+        // xorshift gets stuck at zero, so mix the seed into a nonzero state first
+        Rng(seed.wrapping_mul(0x9E37_79B9_7F4A_7C15) | 1)
+    }
+
+    fn next(&mut self) -> u64 {
+        self.0 ^= self.0 << 13;
+        self.0 ^= self.0 >> 7;
+        self.0 ^= self.0 << 17;
+        self.0
+    }
+
+    // returns a number in 0..n
+    fn below(&mut self, n: usize) -> usize {
+        (self.next() % n as u64) as usize
+    }
+}
+
+// carves a maze with a randomized depth-first search over the odd coordinates, then knocks out
+// a few extra walls so there are loops to lead the Minotaur around
+fn carve(width: usize, height: usize, rng: &mut Rng) -> Vec<Vec<bool>> {
+    let mut walls = vec![vec![true; width]; height];
+    let mut stack = vec![(1, 1)];
+    walls[1][1] = false;
+    while let Some(&(x, y)) = stack.last() {
+        let mut options = Vec::new();
+        if x >= 3 && walls[y][x - 2] {
+            options.push((x - 2, y));
+        }
+        if x + 2 < width - 1 && walls[y][x + 2] {
+            options.push((x + 2, y));
+        }
+        if y >= 3 && walls[y - 2][x] {
+            options.push((x, y - 2));
+        }
+        if y + 2 < height - 1 && walls[y + 2][x] {
+            options.push((x, y + 2));
+        }
+        if options.is_empty() {
+            stack.pop();
+            continue;
+        }
+        let (nx, ny) = options[rng.below(options.len())];
+        walls[(y + ny) / 2][(x + nx) / 2] = false;
+        walls[ny][nx] = false;
+        stack.push((nx, ny));
+    }
+    // the maze only reaches odd coordinates, which would leave the last inside column or row of
+    // a board of even size solid, so those copy the column or row beside them instead
+    if width.is_multiple_of(2) {
+        for row in &mut walls {
+            row[width - 2] = row[width - 3];
+        }
+    }
+    if height.is_multiple_of(2) {
+        walls[height - 2] = walls[height - 3].clone();
+    }
+    // open up roughly one in six of the walls that separate two corridors
+    for y in 1..height - 1 {
+        for x in 1..width - 1 {
+            let horizontal = !walls[y][x - 1] && !walls[y][x + 1];
+            let vertical = !walls[y - 1][x] && !walls[y + 1][x];
+            if walls[y][x] && (horizontal != vertical) && rng.below(6) == 0 {
+                walls[y][x] = false;
+            }
+        }
+    }
+    walls
+}
+
+// turns a wall map and the three piece positions into board text
+fn to_board(walls: &[Vec<bool>], pieces: [(usize, usize); 3]) -> String {
+    let mut board = String::new();
+    for (y, row) in walls.iter().enumerate() {
+        for (x, &wall) in row.iter().enumerate() {
+            board.push(match (x, y) {
+                pos if pos == pieces[0] => 'T',
+                pos if pos == pieces[1] => 'M',
+                pos if pos == pieces[2] => 'G',
+                _ if wall => 'X',
+                _ => ' ',
+            });
+        }
+        board.push('\n');
+    }
+    board
+}
+
+/// Generates a random board in the format read by `Game::from_board`. Only boards that can be
+/// won in at least `options.min_moves` moves are returned.
+pub fn generate(options: &GeneratorOptions) -> Result<String, GenerateError> {
+    if options.width < 5 || options.height < 5 {
+        return Err(GenerateError::TooSmall);
+    }
+    let mut rng = Rng::new(options.seed);
+    for _ in 0..MAX_ATTEMPTS {
+        let walls = carve(options.width, options.height, &mut rng);
+        let mut open = Vec::new();
+        for (y, row) in walls.iter().enumerate() {
+            for (x, &wall) in row.iter().enumerate() {
+                if !wall {
+                    open.push((x, y));
+                }
+            }
+        }
+        // pick three distinct open cells for Theseus, the Minotaur and the goal
+        let mut pieces = [(0, 0); 3];
+        for piece in pieces.iter_mut() {
+            *piece = open.swap_remove(rng.below(open.len()));
+        }
+        let board = to_board(&walls, pieces);
+        let game = Game::from_board(&board).expect("generated board should be valid");
+        if let Ok(commands) = game.solve() {
+            if commands.len() >= options.min_moves {
+                return Ok(board);
+            }
+        }
+    }
+    Err(GenerateError::NoSolvableBoard)
+}
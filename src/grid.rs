@@ -0,0 +1,109 @@
+use crate::Position;
+
+/// What fills a single cell of a `Grid`
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum Cell {
+    /// Floor that any piece can stand on
+    Open,
+    Wall,
+    /// An opening in the outer wall that Theseus can escape through
+    Exit,
+}
+
+/// The walls of a board where walls fill whole cells. Rows don't all have to be the same length;
+/// cells past the end of a short row aren't part of the grid, so `get` returns None for them.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Grid {
+    pub(crate) board: Vec<Vec<bool>>, // true = wall
+    // openings in the outer wall that only Theseus can step into, escaping the maze
+    pub(crate) exits: Vec<Position>,
+}
+
+impl Grid {
+    /// Creates a grid from a matrix of rows, where `true` is a wall
+    pub fn from_walls(walls: Vec<Vec<bool>>) -> Grid {
+        Grid {
+            board: walls,
+            exits: Vec::new(),
+        }
+    }
+
+    /// Creates a grid from a matrix of rows of cells
+    pub fn from_cells(cells: Vec<Vec<Cell>>) -> Grid {
+        let mut exits = Vec::new();
+        let mut board = Vec::new();
+        for (row, cells) in cells.into_iter().enumerate() {
+            let mut walls = Vec::new();
+            for (col, cell) in cells.into_iter().enumerate() {
+                if cell == Cell::Exit {
+                    exits.push(Position::new(row, col));
+                }
+                walls.push(cell == Cell::Wall);
+            }
+            board.push(walls);
+        }
+        Grid { board, exits }
+    }
+
+    /// The length of the longest row
+    pub fn width(&self) -> usize {
+        self.board.iter().map(|row| row.len()).max().unwrap_or(0)
+    }
+
+    pub fn height(&self) -> usize {
+        self.board.len()
+    }
+
+    /// Returns true if every row is the same length
+    pub fn is_rectangular(&self) -> bool {
+        self.board.iter().all(|row| row.len() == self.width())
+    }
+
+    /// Returns the cell at `pos`, or None if it's off the grid
+    pub fn get(&self, pos: Position) -> Option<Cell> {
+        let wall = *self.board.get(pos.row)?.get(pos.col)?;
+        Some(if wall {
+            Cell::Wall
+        } else if self.exits.contains(&pos) {
+            Cell::Exit
+        } else {
+            Cell::Open
+        })
+    }
+
+    /// Iterates over every cell in the grid, row by row from the top left
+    pub fn cells(&self) -> impl Iterator<Item = (Position, Cell)> + '_ {
+        self.board.iter().enumerate().flat_map(move |(row, walls)| {
+            (0..walls.len()).map(move |col| {
+                let pos = Position::new(row, col);
+                (pos, self.get(pos).expect("cell is on the grid"))
+            })
+        })
+    }
+
+    /// Iterates over the position of every wall, row by row from the top left
+    pub fn walls(&self) -> impl Iterator<Item = Position> + '_ {
+        self.cells()
+            .filter(|&(_, cell)| cell == Cell::Wall)
+            .map(|(pos, _)| pos)
+    }
+
+    /// Returns the grid as a matrix of rows, where `true` is a wall. Exits are left open.
+    pub fn to_walls(&self) -> Vec<Vec<bool>> {
+        self.board.clone()
+    }
+
+    // fills out short rows with walls, so every row is as long as the longest one
+    pub(crate) fn pad(&mut self) {
+        let width = self.width();
+        for row in &mut self.board {
+            row.resize(width, true);
+        }
+    }
+}
+
+impl From<Vec<Vec<bool>>> for Grid {
+    fn from(walls: Vec<Vec<bool>>) -> Grid {
+        Grid::from_walls(walls)
+    }
+}
@@ -0,0 +1,58 @@
+use crate::{Command, Game};
+
+/// Snapshots of a game taken at the start of each turn, so turns can be undone and redone
+#[derive(Clone, Default)]
+pub struct History {
+    // each entry is the game before a turn along with the command played that turn
+    undo: Vec<(Game, Command)>,
+    // each entry is the game after an undone turn along with the command played that turn
+    redo: Vec<(Game, Command)>,
+}
+
+impl History {
+    pub fn new() -> History {
+        History::default()
+    }
+
+    /// Records the state of the game before `command` is played. This discards anything that
+    /// could have been redone, since the game has taken a new branch.
+    pub fn record(&mut self, before: &Game, command: Command) {
+        self.undo.push((before.clone(), command));
+        self.redo.clear();
+    }
+
+    /// Rewinds the game to the start of the previous turn, returning false if there is no turn
+    /// to undo
+    pub fn undo(&mut self, game: &mut Game) -> bool {
+        match self.undo.pop() {
+            Some((before, command)) => {
+                let after = std::mem::replace(game, before);
+                self.redo.push((after, command));
+                true
+            }
+            None => false,
+        }
+    }
+
+    /// Replays the most recently undone turn, returning false if there is no turn to redo
+    pub fn redo(&mut self, game: &mut Game) -> bool {
+        match self.redo.pop() {
+            Some((after, command)) => {
+                let before = std::mem::replace(game, after);
+                self.undo.push((before, command));
+                true
+            }
+            None => false,
+        }
+    }
+
+    /// Returns the number of turns played to reach the current state
+    pub fn turns(&self) -> usize {
+        self.undo.len()
+    }
+
+    /// Returns the commands played to reach the current state, oldest first
+    pub fn commands(&self) -> Vec<Command> {
+        self.undo.iter().map(|(_, command)| *command).collect()
+    }
+}
@@ -0,0 +1,106 @@
+use std::io::{self, IsTerminal, Read};
+use std::process::{Command as Process, Stdio};
+
+use crate::{input, Command};
+
+/// Maps the bytes of a single keypress to a command. Besides the letters accepted by `input`,
+/// the arrow keys move Theseus, space or "." skips a turn, "v" saves and "l" loads.
+pub fn key_command(key: &[u8]) -> Option<Command> {
+    match key {
+        b"\x1b[A" | b"\x1bOA" | b"w" | b"W" => Some(Command::Up),
+        b"\x1b[B" | b"\x1bOB" | b"s" | b"S" => Some(Command::Down),
+        b"\x1b[C" | b"\x1bOC" | b"d" | b"D" => Some(Command::Right),
+        b"\x1b[D" | b"\x1bOD" | b"a" | b"A" => Some(Command::Left),
+        b" " | b"." => Some(Command::Skip),
+        b"u" | b"U" => Some(Command::Undo),
+        b"r" | b"R" => Some(Command::Redo),
+        b"h" | b"H" => Some(Command::Hint),
+        b"v" | b"V" => Some(Command::Save),
+        b"l" | b"L" => Some(Command::Load),
+        _ => None,
+    }
+}
+
+// runs `stty` against the terminal on stdin, returning its output if it succeeded
+fn stty(args: &[&str]) -> Option<String> {
+    let output = Process::new("stty")
+        .args(args)
+        .stdin(Stdio::inherit())
+        .stderr(Stdio::null())
+        .output()
+        .ok()?;
+    if !output.status.success() {
+        return None;
+    }
+    String::from_utf8(output.stdout).ok()
+}
+
+// keeps the terminal from waiting for enter or echoing keys, until dropped
+struct RawMode {
+    saved: String,
+}
+
+impl RawMode {
+    fn enable() -> Option<RawMode> {
+        let saved = stty(&["-g"])?.trim().to_string();
+        // Ctrl-C arrives as a key rather than a signal, so the terminal is always put back
+        stty(&["-icanon", "-echo", "-isig", "min", "1", "time", "0"])?;
+        Some(RawMode { saved })
+    }
+}
+
+impl Drop for RawMode {
+    fn drop(&mut self) {
+        stty(&[&self.saved]);
+    }
+}
+
+/// Reads commands from stdin. When stdin is a terminal, single keypresses are read without
+/// waiting for enter; otherwise, or if the terminal can't be switched over, each command is read
+/// as a line with `input`.
+///
+/// While reading keypresses, Ctrl-C doesn't send a signal, since that would kill the game before
+/// the terminal is put back the way it was. Instead `read` returns an error of kind
+/// `io::ErrorKind::Interrupted`, and the caller should quit and drop the `Keyboard`.
+pub struct Keyboard {
+    raw: Option<RawMode>,
+}
+
+impl Keyboard {
+    /// Sets up keyboard input, only trying single keypresses if `keypresses` is true
+    pub fn new(keypresses: bool) -> Keyboard {
+        let raw = if keypresses && io::stdin().is_terminal() {
+            RawMode::enable()
+        } else {
+            None
+        };
+        Keyboard { raw }
+    }
+
+    /// Returns true if commands are read one keypress at a time
+    pub fn is_raw(&self) -> bool {
+        self.raw.is_some()
+    }
+
+    /// Reads the next command, returning None if it isn't a valid one
+    pub fn read(&mut self) -> io::Result<Option<Command>> {
+        if self.raw.is_none() {
+            return Ok(input(io::stdin().lock()));
+        }
+        let mut stdin = io::stdin().lock();
+        let mut key = vec![0];
+        stdin.read_exact(&mut key)?;
+        if key[0] == 0x03 {
+            return Err(io::Error::new(io::ErrorKind::Interrupted, "Ctrl-C pressed"));
+        }
+        // arrow keys send an escape followed by two more bytes, which arrive right behind it. So
+        // they're only waited for briefly, and an escape pressed on its own is an invalid key.
+        if key[0] == 0x1b {
+            stty(&["min", "0", "time", "1"]);
+            let read = stdin.by_ref().take(2).read_to_end(&mut key);
+            stty(&["min", "1", "time", "0"]);
+            read?;
+        }
+        Ok(key_command(&key))
+    }
+}
@@ -1,6 +1,42 @@
 use std::error::Error;
 use std::fmt::Display;
 use std::io;
+use std::sync::Arc;
+
+mod brain;
+mod campaign;
+mod check;
+mod diagnostic;
+mod generator;
+mod grid;
+mod history;
+mod keys;
+mod pack;
+mod position;
+mod render;
+mod replay;
+mod rules;
+mod save;
+mod solver;
+mod thin;
+mod turn;
+
+pub use brain::{Brain, MinotaurBrain};
+pub use campaign::{InvalidProgress, Progress};
+pub use check::{check_board, check_pack, Problem};
+pub use generator::{generate, GenerateError, GeneratorOptions};
+pub use grid::{Cell, Grid};
+pub use history::History;
+pub use keys::{key_command, Keyboard};
+pub use pack::{parse_pack, Level, PackError, PackErrorKind};
+pub use position::{Direction, Position};
+pub use render::{Renderer, Theme};
+pub use replay::{Replay, ReplayError};
+pub use rules::Ruleset;
+pub use save::SaveError;
+pub use solver::Unsolvable;
+pub use thin::{EdgeGrid, Walls};
+pub use turn::{TurnEvent, TurnOutcome};
 
 #[derive(Clone, Copy, Debug, PartialEq)]
 pub enum GameStatus {
@@ -8,127 +44,429 @@ pub enum GameStatus {
     Lose,
     Continue,
 }
+impl Display for GameStatus {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            GameStatus::Win => write!(f, "win"),
+            GameStatus::Lose => write!(f, "lose"),
+            GameStatus::Continue => write!(f, "continue"),
+        }
+    }
+}
+/// A place in the text of a board, counting lines and columns from 1
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Location {
+    pub line: usize,
+    pub column: usize,
+}
+impl Location {
+    // the location of the character at index `pos.col` of line index `pos.row`
+    pub(crate) fn of(pos: Position) -> Location {
+        Location {
+            line: pos.row + 1,
+            column: pos.col + 1,
+        }
+    }
+}
+impl Display for Location {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "line {}, column {}", self.line, self.column)
+    }
+}
+
 #[derive(Clone, Copy, Debug, PartialEq)]
 pub enum BoardError {
-    InvalidCharacter(char),
+    InvalidCharacter(char, Location),
     InvalidSize,
     NoMinotaur,
     NoTheseus,
+    /// Neither a goal nor an exit to win by
     NoGoal,
-    MultipleMinotaur,
-    MultipleTheseus,
-    MultipleGoal,
+    /// A second Theseus at `at`, after the one at `first`
+    MultipleTheseus {
+        at: Location,
+        first: Location,
+    },
+    /// A second goal at `at`, after the one at `first`
+    MultipleGoal {
+        at: Location,
+        first: Location,
+    },
+    /// An exit that isn't in the outer wall, so there's nowhere to step out to
+    ExitNotOnEdge(Location),
+}
+impl BoardError {
+    /// Returns where in the board the problem is, if it's at one place
+    pub fn location(&self) -> Option<Location> {
+        match *self {
+            BoardError::InvalidCharacter(_, at)
+            | BoardError::MultipleTheseus { at, .. }
+            | BoardError::MultipleGoal { at, .. }
+            | BoardError::ExitNotOnEdge(at) => Some(at),
+            _ => None,
+        }
+    }
+
+    /// Returns where the first of a duplicated piece is
+    pub fn earlier(&self) -> Option<Location> {
+        match *self {
+            BoardError::MultipleTheseus { first, .. } | BoardError::MultipleGoal { first, .. } => {
+                Some(first)
+            }
+            _ => None,
+        }
+    }
+
+    // moves the locations down by `lines`, for a board that starts partway through a file
+    pub(crate) fn offset_lines(self, lines: usize) -> BoardError {
+        let shift = |at: Location| Location {
+            line: at.line + lines,
+            ..at
+        };
+        match self {
+            BoardError::InvalidCharacter(c, at) => BoardError::InvalidCharacter(c, shift(at)),
+            BoardError::MultipleTheseus { at, first } => BoardError::MultipleTheseus {
+                at: shift(at),
+                first: shift(first),
+            },
+            BoardError::MultipleGoal { at, first } => BoardError::MultipleGoal {
+                at: shift(at),
+                first: shift(first),
+            },
+            BoardError::ExitNotOnEdge(at) => BoardError::ExitNotOnEdge(shift(at)),
+            other => other,
+        }
+    }
 }
 impl Display for BoardError {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         match self {
-            BoardError::InvalidCharacter(c) => write!(f, "Invalid character: {}", c),
+            BoardError::InvalidCharacter(c, at) => {
+                write!(f, "Invalid character: {:?} at {}", c, at)
+            }
             BoardError::InvalidSize => write!(f, "Invalid size"),
             BoardError::NoMinotaur => write!(f, "No minotaur"),
             BoardError::NoTheseus => write!(f, "No theseus"),
-            BoardError::NoGoal => write!(f, "No goal"),
-            BoardError::MultipleMinotaur => write!(f, "Multiple minotaur"),
-            BoardError::MultipleTheseus => write!(f, "Multiple theseus"),
-            BoardError::MultipleGoal => write!(f, "Multiple goal"),
+            BoardError::NoGoal => write!(f, "No goal or exit"),
+            BoardError::MultipleTheseus { at, first } => {
+                write!(f, "Multiple theseus at {} (first at {})", at, first)
+            }
+            BoardError::MultipleGoal { at, first } => {
+                write!(f, "Multiple goal at {} (first at {})", at, first)
+            }
+            BoardError::ExitNotOnEdge(at) => write!(f, "Exit not in the outer wall at {}", at),
         }
     }
 }
 impl Error for BoardError {}
 
-#[derive(Clone)]
-pub struct Grid {
-    board: Vec<Vec<bool>>, // true = wall
-}
-impl Grid {}
-
-#[derive(Clone)]
-pub struct Game {
-    grid: Grid,
-    theseus: (usize, usize),
-    minotaur: (usize, usize),
-    goal: (usize, usize),
+// the walls of a board, which either fill whole cells or sit on the edges between them
+#[derive(Clone, Debug, PartialEq)]
+enum Layout {
+    Cells(Grid),
+    Edges(EdgeGrid),
 }
 
-impl Game {
-    pub fn from_board(board: &str) -> Result<Game, BoardError> {
-        let mut board_vec: Vec<Vec<bool>> = Vec::new();
-        let mut theseus: Option<(usize, usize)> = None;
-        let mut minotaur: Option<(usize, usize)> = None;
-        let mut goal: Option<(usize, usize)> = None;
-        let mut line_number: usize = 0;
-        // helper for setting a position, or erroring if it's already been set
-        fn check_and_set(
-            pos: &mut Option<(usize, usize)>,
-            x: usize,
-            y: usize,
-            err: BoardError,
-        ) -> Result<(), BoardError> {
-            match *pos {
-                None => {
-                    *pos = Some((x, y));
-                    Ok(())
-                }
-                _ => Err(err),
-            }
+impl Layout {
+    // parses the walls of a board in either format, returning the pieces found on it separately
+    fn parse(board: &str) -> Result<(Layout, Pieces), BoardError> {
+        if board.trim_start().starts_with('+') {
+            let (edges, pieces) = thin::parse_edges(board)?;
+            Ok((Layout::Edges(edges), pieces))
+        } else {
+            let (grid, pieces) = parse_cells(board)?;
+            Ok((Layout::Cells(grid), pieces))
         }
-        // parse each line into a row
-        for line in board.lines() {
-            let mut row: Vec<bool> = Vec::new();
-            let mut col: usize = 0;
-            // parse each character into a column in the row
-            for c in line.chars() {
-                match c {
-                    'X' => {
-                        row.push(true);
-                    }
-                    'T' => {
-                        check_and_set(&mut theseus, col, line_number, BoardError::MultipleTheseus)?;
-                        row.push(false);
-                    }
-                    'M' => {
-                        check_and_set(
-                            &mut minotaur,
-                            col,
-                            line_number,
-                            BoardError::MultipleMinotaur,
-                        )?;
-                        row.push(false);
-                    }
-                    'G' => {
-                        check_and_set(&mut goal, col, line_number, BoardError::MultipleGoal)?;
-                        row.push(false);
-                    }
-                    ' ' => {
-                        row.push(false);
-                    }
-                    bad => {
-                        return Err(BoardError::InvalidCharacter(bad));
+    }
+
+    // writes out the walls and exits in the same format they're parsed from, with no pieces on
+    // the board
+    fn to_text(&self) -> String {
+        match self {
+            Layout::Cells(grid) => {
+                let mut text = String::new();
+                for (y, row) in grid.board.iter().enumerate() {
+                    for x in 0..row.len() {
+                        text.push(match grid.get(Position::new(y, x)) {
+                            Some(Cell::Wall) => 'X',
+                            Some(Cell::Exit) => 'E',
+                            _ => ' ',
+                        });
                     }
+                    text.push('\n');
                 }
-                col += 1;
+                text
             }
-            board_vec.push(row);
-            line_number += 1;
+            Layout::Edges(edges) => edges.to_text(),
+        }
+    }
+
+    // returns true if Theseus escapes by stepping from `pos` in `direction`
+    fn exits_through(&self, pos: Position, direction: Direction) -> bool {
+        match self {
+            Layout::Cells(grid) => pos
+                .step(direction)
+                .is_some_and(|exit| grid.exits.contains(&exit)),
+            Layout::Edges(edges) => edges.exits.contains(&(pos, direction)),
         }
-        // check that all is well and return
-        if theseus == None {
-            return Err(BoardError::NoTheseus);
+    }
+
+    fn has_exits(&self) -> bool {
+        match self {
+            Layout::Cells(grid) => !grid.exits.is_empty(),
+            Layout::Edges(edges) => !edges.exits.is_empty(),
         }
-        if minotaur == None {
+    }
+
+    // where each exit is drawn, in the coordinates `Game::show` draws the board in
+    fn exit_marks(&self) -> Vec<Position> {
+        match self {
+            Layout::Cells(grid) => grid.exits.clone(),
+            Layout::Edges(edges) => edges
+                .exits
+                .iter()
+                .filter_map(|&(pos, direction)| thin::expanded(pos).step(direction))
+                .collect(),
+        }
+    }
+}
+
+// helper for setting a position, or erroring if it's already been set. `err` is given the
+// position that was set first.
+fn check_and_set(
+    pos: &mut Option<Position>,
+    new: Position,
+    err: impl FnOnce(Position) -> BoardError,
+) -> Result<(), BoardError> {
+    match *pos {
+        None => {
+            *pos = Some(new);
+            Ok(())
+        }
+        Some(first) => Err(err(first)),
+    }
+}
+
+/// A board and where each piece is on it.
+///
+/// Every position outside the board counts as a wall: above the first row, left of the first
+/// column, below the last row, and past the end of any row that's shorter than the others. Nothing
+/// can ever step there, and `is_wall` is true for all of them. Open cells on the edge of a board
+/// can still be stood on, so a goal can sit in a gap in the outer wall.
+///
+/// Boards can also mark exits: openings in the outer wall that Theseus wins by stepping out
+/// through, as in the original puzzle. Minotaurs can never leave the board, so for them an exit is
+/// as good as a wall. A board needs a goal, at least one exit, or both.
+#[derive(Clone)]
+pub struct Game {
+    layout: Layout,
+    theseus: Position,
+    // in the order they move, which starts out as the order they appear on the board
+    minotaurs: Vec<Position>,
+    goal: Option<Position>,
+    // true once Theseus has stepped out through an exit, leaving him where he stepped from
+    escaped: bool,
+    brain: Arc<dyn MinotaurBrain>,
+    rules: Ruleset,
+}
+
+// the pieces found while parsing a board, before checking that they're all there
+#[derive(Default)]
+struct Pieces {
+    theseus: Option<Position>,
+    minotaurs: Vec<Position>,
+    goal: Option<Position>,
+}
+
+impl Pieces {
+    // checks that every piece was found and puts them on the board
+    fn into_game(self, layout: Layout) -> Result<Game, BoardError> {
+        let theseus = self.theseus.ok_or(BoardError::NoTheseus)?;
+        if self.minotaurs.is_empty() {
             return Err(BoardError::NoMinotaur);
         }
-        if goal == None {
+        if self.goal.is_none() && !layout.has_exits() {
             return Err(BoardError::NoGoal);
         }
         Ok(Game {
-            grid: Grid { board: board_vec },
-            theseus: theseus.unwrap(),
-            minotaur: minotaur.unwrap(),
-            goal: goal.unwrap(),
+            layout,
+            theseus,
+            minotaurs: self.minotaurs,
+            goal: self.goal,
+            escaped: false,
+            brain: Arc::new(Brain::Classic),
+            rules: Ruleset::default(),
         })
     }
+}
+
+// parses a board where walls fill whole cells, without checking which pieces are on it
+fn parse_cells(board: &str) -> Result<(Grid, Pieces), BoardError> {
+    let mut board_vec: Vec<Vec<bool>> = Vec::new();
+    let mut exits = Vec::new();
+    let mut pieces = Pieces::default();
+    // parse each line into a row
+    for (line_number, line) in board.lines().enumerate() {
+        let mut row: Vec<bool> = Vec::new();
+        // parse each character into a column in the row
+        for (col, c) in line.chars().enumerate() {
+            let pos = Position::new(line_number, col);
+            match c {
+                'X' => {
+                    row.push(true);
+                }
+                'T' => {
+                    check_and_set(&mut pieces.theseus, pos, |first| {
+                        BoardError::MultipleTheseus {
+                            at: Location::of(pos),
+                            first: Location::of(first),
+                        }
+                    })?;
+                    row.push(false);
+                }
+                'M' => {
+                    pieces.minotaurs.push(pos);
+                    row.push(false);
+                }
+                'G' => {
+                    check_and_set(&mut pieces.goal, pos, |first| BoardError::MultipleGoal {
+                        at: Location::of(pos),
+                        first: Location::of(first),
+                    })?;
+                    row.push(false);
+                }
+                'E' => {
+                    exits.push(pos);
+                    row.push(false);
+                }
+                ' ' => {
+                    row.push(false);
+                }
+                bad => {
+                    return Err(BoardError::InvalidCharacter(bad, Location::of(pos)));
+                }
+            }
+        }
+        board_vec.push(row);
+    }
+    // an exit has to be on the edge of the board, with somewhere off it to step out to
+    let on_board = |pos: Option<Position>| {
+        pos.is_some_and(|pos| {
+            board_vec
+                .get(pos.row)
+                .is_some_and(|row| pos.col < row.len())
+        })
+    };
+    if let Some(&exit) = exits
+        .iter()
+        .find(|exit| Direction::ALL.iter().all(|&d| on_board(exit.step(d))))
+    {
+        return Err(BoardError::ExitNotOnEdge(Location::of(exit)));
+    }
+    let grid = Grid {
+        board: board_vec,
+        exits,
+    };
+    Ok((grid, pieces))
+}
+
+impl Game {
+    /// Parses a board in either format: walls filling whole cells, or thin walls like
+    /// `from_thin_board` reads if the board starts with '+'
+    pub fn from_board(board: &str) -> Result<Game, BoardError> {
+        let (layout, pieces) = Layout::parse(board)?;
+        pieces.into_game(layout)
+    }
+
+    /// Parses a board whose walls fill whole cells, but fills out rows shorter than the longest one with
+    /// walls so the grid is rectangular. The game plays exactly the same either way, since
+    /// positions past the end of a row already count as walls.
+    pub fn from_board_padded(board: &str) -> Result<Game, BoardError> {
+        let (mut grid, pieces) = parse_cells(board)?;
+        grid.pad();
+        pieces.into_game(Layout::Cells(grid))
+    }
+
+    /// Parses a thin-wall board, where walls sit between cells instead of filling them
+    pub fn from_thin_board(board: &str) -> Result<Game, BoardError> {
+        thin::parse(board)
+    }
+
+    /// Parses a board in either format, the same as `from_board`
+    pub fn parse(board: &str) -> Result<Game, BoardError> {
+        Game::from_board(board)
+    }
+
+    /// Writes out the board with every piece where it is now, or returns None if the board text
+    /// can't hold the game as it stands: when two pieces share a cell, such as a Minotaur on the
+    /// goal, Theseus has escaped, the Minotaurs move in a different order than they appear on the
+    /// board, or the brain or rules aren't the defaults. Those need `save_state` instead.
+    ///
+    /// The board is written in the format it was read in, and whenever it's written,
+    /// `from_board` gives back a game equal to this one.
+    pub fn to_board(&self) -> Option<String> {
+        let mut pieces: Vec<(Position, char)> =
+            self.goal.map(|goal| (goal, 'G')).into_iter().collect();
+        pieces.push((self.theseus, 'T'));
+        pieces.extend(self.minotaurs.iter().map(|&minotaur| (minotaur, 'M')));
+        let shared =
+            (1..pieces.len()).any(|i| pieces[i..].iter().any(|&(pos, _)| pos == pieces[i - 1].0));
+        if shared
+            || self.escaped
+            || !self.minotaurs.is_sorted()
+            || self.brain.name() != Brain::Classic.name()
+            || self.rules != Ruleset::default()
+        {
+            return None;
+        }
+        let mut lines: Vec<Vec<char>> = self
+            .layout
+            .to_text()
+            .lines()
+            .map(|line| line.chars().collect())
+            .collect();
+        for (pos, c) in pieces {
+            let at = match &self.layout {
+                Layout::Cells(_) => pos,
+                Layout::Edges(_) => thin::expanded(pos),
+            };
+            lines[at.row][at.col] = c;
+        }
+        let board = lines
+            .into_iter()
+            .map(|line| line.into_iter().chain(['\n']).collect::<String>())
+            .collect();
+        Some(board)
+    }
+
+    /// Writes out the full state of the game, including where every piece has moved to, so it can
+    /// be restored exactly with `Game::load_state`
+    pub fn save_state(&self) -> String {
+        save::save(self)
+    }
+
+    /// Restores a game written by `Game::save_state`
+    pub fn load_state(text: &str) -> Result<Game, SaveError> {
+        save::load(text)
+    }
 
     pub fn show(&self) {
+        print!("{}", self);
+    }
+
+    /// Prints the board like `show`, coloring each kind of glyph as set by `theme`
+    pub fn show_with_theme(&self, theme: &Theme) {
+        print!("{}", self.render(theme));
+    }
+
+    /// Writes the board drawn by `render` into `out`
+    pub fn render_to(&self, out: &mut impl io::Write, theme: &Theme) -> io::Result<()> {
+        out.write_all(self.render(theme).as_bytes())
+    }
+
+    /// Draws the board the way `show_with_theme` prints it, with walls in box-drawing characters
+    /// and each line ending in a newline. The `Display` impl draws it with `Theme::plain()`.
+    pub fn render(&self, theme: &Theme) -> String {
         // helper for choosing a wall character
         // each argument is true if there is another wall in that direction
         fn get_wall_char(left: bool, right: bool, up: bool, down: bool) -> char {
@@ -150,133 +488,257 @@ impl Game {
         let theseus_char = 'T';
         let minotaur_char = 'M';
         let goal_char = 'G';
-        for y in 0..self.grid.board.len() {
+        let exit_char = 'E';
+        let exits = self.layout.exit_marks();
+        // thin-wall boards are drawn on a grid twice as fine, with the cells at odd coordinates
+        let walls = match &self.layout {
+            Layout::Cells(grid) => grid.board.clone(),
+            Layout::Edges(edges) => edges.expand(),
+        };
+        let cell_at = |x: usize, y: usize| match &self.layout {
+            Layout::Cells(_) => Some(Position::new(y, x)),
+            Layout::Edges(_) if x % 2 == 1 && y % 2 == 1 => Some(Position::new(y / 2, x / 2)),
+            Layout::Edges(_) => None,
+        };
+        let mut text = String::new();
+        for y in 0..walls.len() {
             let mut line = String::from("");
-            for x in 0..self.grid.board[y].len() {
-                if (x, y) == self.minotaur {
-                    line.push(minotaur_char);
+            for x in 0..walls[y].len() {
+                let cell = cell_at(x, y);
+                if cell.is_some_and(|cell| self.minotaurs.contains(&cell)) {
+                    Theme::paint(&mut line, minotaur_char, theme.minotaur);
+                    continue;
+                }
+                // once Theseus escapes he's no longer on the board
+                if cell == Some(self.theseus) && !self.escaped {
+                    Theme::paint(&mut line, theseus_char, theme.theseus);
                     continue;
                 }
-                if (x, y) == self.theseus {
-                    line.push(theseus_char);
+                if cell.is_some() && cell == self.goal {
+                    Theme::paint(&mut line, goal_char, theme.goal);
                     continue;
                 }
-                if (x, y) == self.goal {
-                    line.push(goal_char);
+                if exits.contains(&Position::new(y, x)) {
+                    Theme::paint(&mut line, exit_char, theme.goal);
                     continue;
                 }
-                if !self.grid.board[y][x] {
+                if !walls[y][x] {
                     line.push(' ');
                     continue;
                 }
-                let left = x > 0 && self.grid.board[y][x - 1];
-                let right = x + 1 < self.grid.board[y].len() && self.grid.board[y][x + 1];
-                let up = y > 0 && x < self.grid.board[y - 1].len() && self.grid.board[y - 1][x];
-                let down = y + 1 < self.grid.board.len()
-                    && x < self.grid.board[y + 1].len()
-                    && self.grid.board[y + 1][x];
-                line.push(get_wall_char(left, right, up, down));
+                let left = x > 0 && walls[y][x - 1];
+                let right = x + 1 < walls[y].len() && walls[y][x + 1];
+                let up = y > 0 && x < walls[y - 1].len() && walls[y - 1][x];
+                let down = y + 1 < walls.len() && x < walls[y + 1].len() && walls[y + 1][x];
+                Theme::paint(&mut line, get_wall_char(left, right, up, down), theme.wall);
             }
-            println!("{}", line);
+            text.push_str(&line);
+            text.push('\n');
         }
+        text
     }
 
-    pub fn minotaur_move(&mut self) {
-        // if the minotaur is left of theseus and it can go right, go right
-        if self.minotaur.0 < self.theseus.0
-            && self.minotaur.0 + 1 < self.grid.board[self.minotaur.1].len()
-            && !self.is_wall(self.minotaur.1, self.minotaur.0 + 1)
-        {
-            self.minotaur.0 += 1;
-            return;
-        }
-        // if the minotaur is right of theseus and it can go left, go left
-        if self.minotaur.0 > self.theseus.0 && !self.is_wall(self.minotaur.1, self.minotaur.0 - 1) {
-            self.minotaur.0 -= 1;
-            return;
-        }
-        // if the minotaur is above theseus and it can go down, go down
-        if self.minotaur.1 < self.theseus.1
-            && self.minotaur.0 < self.grid.board[self.minotaur.1 + 1].len()
-            && !self.is_wall(self.minotaur.1 + 1, self.minotaur.0)
-        {
-            self.minotaur.1 += 1;
-            return;
-        }
-        // if the minotaur is below theseus and it can go up, go up
-        if self.minotaur.1 > self.theseus.1
-            && self.minotaur.0 < self.grid.board[self.minotaur.1 - 1].len()
-            && !self.is_wall(self.minotaur.1 - 1, self.minotaur.0)
-        {
-            self.minotaur.1 -= 1;
-            return;
+    /// Returns the position one step from `pos` in `direction`, or None if a wall is in the way.
+    /// Every position off the board counts as a wall, and so does every exit, since only Theseus
+    /// can leave through them.
+    pub fn step(&self, pos: Position, direction: Direction) -> Option<Position> {
+        match &self.layout {
+            Layout::Cells(grid) => {
+                let next = pos.step(direction)?;
+                // only Theseus can go through an exit, and only by leaving
+                match grid.get(next) {
+                    Some(Cell::Open) => Some(next),
+                    _ => None,
+                }
+            }
+            Layout::Edges(edges) => edges.step(pos, direction),
         }
-        // if no moves which put it closer are possible, it does nothing
     }
 
+    /// Like `step`, but for a Minotaur, which also can't step onto the goal if the rules say so
+    pub fn minotaur_step(&self, pos: Position, direction: Direction) -> Option<Position> {
+        self.step(pos, direction)
+            .filter(|&next| self.rules.minotaurs_enter_goal || Some(next) != self.goal)
+    }
+
+    /// Moves every Minotaur one step where the brain says, one after another in order. A Minotaur
+    /// that lands on a cell another Minotaur is already standing on merges into it, so only the
+    /// first one is kept.
+    pub fn minotaur_move(&mut self) {
+        self.move_minotaurs(&mut Vec::new());
+    }
+
+    /// Moves Theseus one step, or out through an exit. Moves into walls and commands that aren't
+    /// moves are ignored.
     pub fn theseus_move(&mut self, command: Command) {
-        if command == Command::Left
-            && self.theseus.0 > 0
-            && !self.is_wall(self.theseus.1, self.theseus.0 - 1)
-        {
-            self.theseus.0 -= 1;
-        }
-        if command == Command::Right
-            && self.theseus.0 < self.grid.board[self.theseus.1].len()
-            && !self.is_wall(self.theseus.1, self.theseus.0 + 1)
-        {
-            self.theseus.0 += 1;
-        }
-        if command == Command::Up
-            && self.theseus.1 > 0
-            && self.theseus.0 < self.grid.board[self.theseus.1 - 1].len()
-            && !self.is_wall(self.theseus.1 - 1, self.theseus.0)
-        {
-            self.theseus.1 -= 1;
-        }
-        if command == Command::Down
-            && self.theseus.1 < self.grid.board.len()
-            && self.theseus.0 < self.grid.board[self.theseus.1 + 1].len()
-            && !self.is_wall(self.theseus.1 + 1, self.theseus.0)
-        {
-            self.theseus.1 += 1;
+        self.move_theseus(command, &mut Vec::new());
+    }
+
+    /// Returns where Theseus is. After he escapes, this is where he stepped out from.
+    pub fn theseus_pos(&self) -> Position {
+        self.theseus
+    }
+
+    /// Returns where the first Minotaur to move is, which on a board with one Minotaur is the
+    /// only one
+    pub fn minotaur_pos(&self) -> Position {
+        self.minotaurs[0]
+    }
+
+    /// Returns where every Minotaur is, in the order they move
+    pub fn minotaur_positions(&self) -> &[Position] {
+        &self.minotaurs
+    }
+
+    /// Returns the brain that decides how the Minotaurs move
+    pub fn brain(&self) -> &dyn MinotaurBrain {
+        self.brain.as_ref()
+    }
+
+    /// Changes how the Minotaurs move from now on. Boards start out with `Brain::Classic`.
+    pub fn set_brain(&mut self, brain: impl MinotaurBrain + 'static) {
+        self.brain = Arc::new(brain);
+    }
+
+    /// Returns the rules the game is played by
+    pub fn rules(&self) -> Ruleset {
+        self.rules
+    }
+
+    /// Changes the rules the game is played by from the next turn on. Boards start out with
+    /// `Ruleset::default()`.
+    pub fn set_rules(&mut self, rules: Ruleset) {
+        self.rules = rules;
+    }
+
+    /// Returns the walls of the board, or None if it's a thin-wall board
+    pub fn grid(&self) -> Option<&Grid> {
+        match &self.layout {
+            Layout::Cells(grid) => Some(grid),
+            Layout::Edges(_) => None,
         }
     }
 
+    /// Returns where the goal is, or None if the board is won only by its exits
+    pub fn goal_pos(&self) -> Option<Position> {
+        self.goal
+    }
+
+    /// Returns true if Theseus has escaped the maze through an exit
+    pub fn has_escaped(&self) -> bool {
+        self.escaped
+    }
+
+    /// Returns how the game stands between turns. Theseus wins on the goal or once he's escaped,
+    /// even if a Minotaur is standing there too, and loses if he shares a cell with a Minotaur.
+    /// Whether that counts partway through a turn is up to the `Ruleset`.
     pub fn status(&self) -> GameStatus {
-        if self.theseus == self.goal {
+        if self.escaped || Some(self.theseus) == self.goal {
             GameStatus::Win
-        } else if self.theseus == self.minotaur {
+        } else if self.minotaurs.contains(&self.theseus) {
             GameStatus::Lose
         } else {
             GameStatus::Continue
         }
     }
+
+    /// Plays a full turn for each command in order without any output, stopping early if the game
+    /// ends, and returns the resulting status
+    pub fn play_moves(&mut self, commands: &[Command]) -> GameStatus {
+        for &cmd in commands {
+            if self.status() != GameStatus::Continue {
+                break;
+            }
+            self.play_turn(cmd);
+        }
+        self.status()
+    }
+
+    /// Returns the shortest list of commands that wins the game from its current state
+    pub fn solve(&self) -> Result<Vec<Command>, Unsolvable> {
+        solver::solve(self)
+    }
+
+    /// Returns a move that still leads to a win, or None if the game can no longer be won
+    pub fn hint(&self) -> Option<Command> {
+        self.solve().ok()?.first().copied()
+    }
 }
 
 impl Game {
     /// Returns true if the given position is Theseus
     pub fn is_theseus(&self, row: usize, col: usize) -> bool {
-        (col, row) == self.theseus
+        Position::new(row, col) == self.theseus
     }
-    /// Returns true if the given position is Minotaur
+    /// Returns true if the given position is a Minotaur
     pub fn is_minotaur(&self, row: usize, col: usize) -> bool {
-        (col, row) == self.minotaur
+        self.minotaurs.contains(&Position::new(row, col))
     }
-    /// Returns true if the given position is a wall
+    /// Returns true if the given position is a wall, including any position off the board
     pub fn is_wall(&self, row: usize, col: usize) -> bool {
-        self.grid.board[row][col]
+        match &self.layout {
+            Layout::Cells(grid) => grid
+                .get(Position::new(row, col))
+                .is_none_or(|cell| cell == Cell::Wall),
+            // thin-wall boards have no wall cells, only walls between them, so only cells off the
+            // board are walls
+            Layout::Edges(edges) => edges.walls(row, col).is_none(),
+        }
     }
     /// Returns true if the given position is the goal
     pub fn is_goal(&self, row: usize, col: usize) -> bool {
-        (col, row) == self.goal
+        Some(Position::new(row, col)) == self.goal
+    }
+    /// Returns true if the given position is an exit in the outer wall. Only boards with whole
+    /// cell walls have exits at a position; on thin-wall boards they're on the edges of cells.
+    pub fn is_exit(&self, row: usize, col: usize) -> bool {
+        match &self.layout {
+            Layout::Cells(grid) => grid.get(Position::new(row, col)) == Some(Cell::Exit),
+            Layout::Edges(_) => false,
+        }
     }
     /// Returns true if the given position is empty
     pub fn is_empty(&self, row: usize, col: usize) -> bool {
         !self.is_wall(row, col)
-            && (col, row) != self.theseus
-            && (col, row) != self.minotaur
-            && (col, row) != self.goal
+            && !self.is_theseus(row, col)
+            && !self.is_minotaur(row, col)
+            && !self.is_goal(row, col)
+            && !self.is_exit(row, col)
+    }
+}
+
+/// Games are equal when they have the same walls, pieces in the same places, the same rules and
+/// brains of the same name
+impl PartialEq for Game {
+    fn eq(&self, other: &Game) -> bool {
+        self.layout == other.layout
+            && self.theseus == other.theseus
+            && self.minotaurs == other.minotaurs
+            && self.goal == other.goal
+            && self.escaped == other.escaped
+            && self.brain.name() == other.brain.name()
+            && self.rules == other.rules
+    }
+}
+
+// the brain is shown by its name, since that's all a `MinotaurBrain` has to show
+impl std::fmt::Debug for Game {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("Game")
+            .field("layout", &self.layout)
+            .field("theseus", &self.theseus)
+            .field("minotaurs", &self.minotaurs)
+            .field("goal", &self.goal)
+            .field("escaped", &self.escaped)
+            .field("brain", &self.brain.name())
+            .field("rules", &self.rules)
+            .finish()
+    }
+}
+
+impl Display for Game {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.render(&Theme::plain()))
     }
 }
 
@@ -292,6 +754,32 @@ pub enum Command {
     Right,
     /// Don't move at all
     Skip,
+    /// Take back the previous turn
+    Undo,
+    /// Replay a turn that was taken back
+    Redo,
+    /// Suggest a move that still leads to a win
+    Hint,
+    /// Save the game to a file
+    Save,
+    /// Restore the game from a file
+    Load,
+}
+impl Display for Command {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Command::Up => write!(f, "up"),
+            Command::Down => write!(f, "down"),
+            Command::Left => write!(f, "left"),
+            Command::Right => write!(f, "right"),
+            Command::Skip => write!(f, "skip"),
+            Command::Undo => write!(f, "undo"),
+            Command::Redo => write!(f, "redo"),
+            Command::Hint => write!(f, "hint"),
+            Command::Save => write!(f, "save"),
+            Command::Load => write!(f, "load"),
+        }
+    }
 }
 
 //  To get a command from the user, you can use the following code:
@@ -309,15 +797,72 @@ pub enum Command {
 //  input however you like, so long as you document it here in a comment and it is reasonable to
 //  use as a player.
 
-// Either "WASD" or literal words are valid
+// Either "WASD" or literal words are valid, plus "u"/"undo" and "r"/"redo" to step through the
+// move history, "h"/"hint" to ask for a suggested move, and "save"/"load" to suspend the game to
+// a file and restore it. When playing in a terminal, `Keyboard` reads single keypresses
+// instead, including the arrow keys, and falls back to this otherwise.
+#[allow(clippy::implied_bounds_in_impls)]
 pub fn input(stdin: impl io::Read + io::BufRead) -> Option<Command> {
     let line = stdin.lines().next().unwrap().unwrap();
-    match line.to_lowercase().as_str() {
+    parse_command(&line)
+}
+
+/// Returned by `parse_moves` for a character that isn't a move
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct InvalidMove(pub char);
+impl Display for InvalidMove {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "Invalid move: {}", self.0)
+    }
+}
+impl Error for InvalidMove {}
+
+/// Parses a compact move string such as `wwdsd.`, where "w", "a", "s" and "d" move Theseus and
+/// "." skips a turn. Whitespace is ignored so long solutions can be split across lines.
+pub fn parse_moves(moves: &str) -> Result<Vec<Command>, InvalidMove> {
+    moves
+        .chars()
+        .filter(|c| !c.is_whitespace())
+        .map(|c| match c.to_ascii_lowercase() {
+            'w' => Ok(Command::Up),
+            'a' => Ok(Command::Left),
+            's' => Ok(Command::Down),
+            'd' => Ok(Command::Right),
+            '.' => Ok(Command::Skip),
+            _ => Err(InvalidMove(c)),
+        })
+        .collect()
+}
+
+/// Formats commands as a compact move string that `parse_moves` can read back. Commands that
+/// aren't moves, like `Command::Undo`, are left out.
+pub fn format_moves(commands: &[Command]) -> String {
+    commands
+        .iter()
+        .filter_map(|cmd| match cmd {
+            Command::Up => Some('w'),
+            Command::Left => Some('a'),
+            Command::Down => Some('s'),
+            Command::Right => Some('d'),
+            Command::Skip => Some('.'),
+            _ => None,
+        })
+        .collect()
+}
+
+/// Parses a single command typed by the player, ignoring case
+pub fn parse_command(word: &str) -> Option<Command> {
+    match word.to_lowercase().as_str() {
         "w" | "up" => Some(Command::Up),
         "a" | "left" => Some(Command::Left),
         "s" | "down" => Some(Command::Down),
         "d" | "right" => Some(Command::Right),
         "skip" => Some(Command::Skip),
+        "u" | "undo" => Some(Command::Undo),
+        "r" | "redo" => Some(Command::Redo),
+        "h" | "hint" => Some(Command::Hint),
+        "save" => Some(Command::Save),
+        "load" => Some(Command::Load),
         _ => None,
     }
 }
@@ -1,80 +1,580 @@
-use std::io::BufReader;
 use theseus::*;
 
 fn wait() {
     std::thread::sleep(std::time::Duration::from_millis(300));
 }
 
-fn show_with_message(game: &Game, message: &str) -> Result<(), Box<dyn std::error::Error>> {
-    game.show();
-    println!("{}", message);
+// settings for the interactive modes, taken from the command line flags
+#[derive(Clone)]
+struct Settings {
+    keypresses: bool,
+    ansi: bool,
+    theme: Theme,
+    save_path: String,
+    // overrides the brain each board's Minotaurs would otherwise use
+    brain: Option<Brain>,
+    // whether the load command is allowed. Modes that record the moves played or count them
+    // turn it off, since a loaded game didn't get where it is by those moves.
+    load: bool,
+}
+
+fn show_with_message(
+    renderer: &Renderer,
+    game: &Game,
+    message: &str,
+) -> Result<(), Box<dyn std::error::Error>> {
+    renderer.draw(&mut std::io::stdout(), game, message)?;
     Ok(())
 }
 
-fn game_over(game: &Game) -> Result<bool, Box<dyn std::error::Error>> {
+fn game_over(renderer: &Renderer, game: &Game) -> Result<bool, Box<dyn std::error::Error>> {
     match game.status() {
         GameStatus::Win => {
-            show_with_message(&game, "You win!")?;
+            show_with_message(renderer, game, "You win!")?;
             Ok(true)
         }
         GameStatus::Lose => {
-            show_with_message(&game, "You lose!")?;
+            show_with_message(renderer, game, "You lose!")?;
             Ok(true)
         }
         GameStatus::Continue => Ok(false),
     }
 }
 
-fn main() -> Result<(), Box<dyn std::error::Error>> {
-    // Read command line arguments
-    if std::env::args().len() != 2 {
-        println!("Usage: theseus <board_path>");
+fn usage() -> ! {
+    println!("Usage: theseus [options] <board_path>");
+    println!("       theseus solve [--brain <name>] <board_path>");
+    println!("       theseus record [options] <board_path> <replay_path>");
+    println!("       theseus replay [options] <replay_path>");
+    println!("       theseus pack [options] <pack_path>");
+    println!("       theseus campaign [options] [--progress <path>] <pack_path>");
+    println!("       theseus run [--brain <name>] <board_path> [moves]");
+    println!("       theseus check <board_or_pack_path>...");
+    println!("       theseus generate --width <n> --height <n> [--seed <n>] [--min-moves <n>]");
+    println!();
+    println!("Options:");
+    println!("  --line-input    type each command followed by enter instead of single keypresses");
+    println!("  --no-ansi       print each frame below the last instead of redrawing in place");
+    println!("  --theme <name>  color the board with classic, high-contrast, colorblind or plain");
+    println!("  --save <path>   where the save and load commands keep the game (theseus.save)");
+    println!("  --brain <name>  move the Minotaurs with classic, vertical, greedy or hunter");
+    std::process::exit(1);
+}
+
+// removes `flag` from the arguments, returning true if it was there
+fn take_flag(args: &mut Vec<String>, flag: &str) -> bool {
+    let count = args.len();
+    args.retain(|arg| arg != flag);
+    args.len() != count
+}
+
+// removes `flag` and the value after it from the arguments, returning the value
+fn take_option(args: &mut Vec<String>, flag: &str) -> Option<String> {
+    let i = args.iter().position(|arg| arg == flag)?;
+    if i + 1 >= args.len() {
+        usage();
+    }
+    args.remove(i);
+    Some(args.remove(i))
+}
+
+fn read_board(board_path: &str) -> String {
+    std::fs::read_to_string(board_path).expect("Cannot find board file")
+}
+
+// parses a board, exiting with a message pointing at the problem if it's invalid
+fn parse_board(board_path: &str, board: &str, brain: Option<Brain>) -> Game {
+    let mut game = Game::parse(board).unwrap_or_else(|err| {
+        eprint!("{}", err.render(board_path, board));
         std::process::exit(1);
+    });
+    if let Some(brain) = brain {
+        game.set_brain(brain);
+    }
+    game
+}
+
+fn load_game(board_path: &str, brain: Option<Brain>) -> Result<Game, Box<dyn std::error::Error>> {
+    Ok(parse_board(board_path, &read_board(board_path), brain))
+}
+
+// reads a level pack, pointing at the problem like `parse_board` if one of its boards is invalid
+fn load_pack(
+    pack_path: &str,
+    brain: Option<Brain>,
+) -> Result<Vec<Level>, Box<dyn std::error::Error>> {
+    let text = std::fs::read_to_string(pack_path).expect("Cannot find level pack");
+    match parse_pack(&text) {
+        Ok(mut levels) => {
+            if let Some(brain) = brain {
+                for level in &mut levels {
+                    level.game.set_brain(brain);
+                }
+            }
+            Ok(levels)
+        }
+        Err(PackError {
+            level,
+            title,
+            kind: PackErrorKind::Board(err),
+        }) => {
+            eprintln!("In level {} ({}):", level, title);
+            eprint!("{}", err.render(pack_path, &text));
+            std::process::exit(1);
+        }
+        Err(err) => Err(err.into()),
+    }
+}
+
+fn solve(board_path: &str, brain: Option<Brain>) -> Result<(), Box<dyn std::error::Error>> {
+    let game = load_game(board_path, brain)?;
+    match game.solve() {
+        Ok(commands) => {
+            println!(
+                "Solved in {} moves: {}",
+                commands.len(),
+                format_moves(&commands)
+            );
+            for (i, cmd) in commands.iter().enumerate() {
+                println!("{:>4}. {}", i + 1, cmd);
+            }
+            Ok(())
+        }
+        Err(err) => {
+            println!("{}", err);
+            std::process::exit(1);
+        }
+    }
+}
+
+// reads commands until a valid one is entered, returning None if the player pressed Ctrl-C
+fn read_command(
+    renderer: &Renderer,
+    game: &Game,
+    keyboard: &mut Keyboard,
+) -> Result<Option<Command>, Box<dyn std::error::Error>> {
+    loop {
+        match keyboard.read() {
+            Ok(Some(cmd)) => return Ok(Some(cmd)),
+            Ok(None) => {
+                show_with_message(renderer, game, "Invalid command. Please try again.")?;
+            }
+            Err(err) if err.kind() == std::io::ErrorKind::Interrupted => return Ok(None),
+            Err(err) => return Err(err.into()),
+        }
     }
-    let board_path = std::env::args().nth(1).unwrap();
-    let board = std::fs::read_to_string(board_path).expect("Cannot find board file");
+}
+
+// plays out one full turn, drawing the board before each of the Minotaurs' steps, and returns
+// true if the game ended during it
+fn take_turn(
+    renderer: &Renderer,
+    game: &mut Game,
+    cmd: Command,
+) -> Result<bool, Box<dyn std::error::Error>> {
+    // the turn can't be stopped partway, so the first error drawing it is kept for afterwards
+    let mut drawn = Ok(());
+    game.play_turn_with(cmd, |game, step| {
+        if drawn.is_ok() {
+            let message = format!("Minotaurs turn {}...", step);
+            drawn = renderer.draw(&mut std::io::stdout(), game, &message);
+            wait();
+        }
+    });
+    drawn?;
+    game_over(renderer, game)
+}
 
-    // Initialize game struct
-    let mut game = Game::from_board(&board)?;
+// runs the interactive game loop until the game is over or the player presses Ctrl-C,
+// returning the final game and the history that led to it
+fn play(
+    mut game: Game,
+    settings: &Settings,
+) -> Result<(Game, History), Box<dyn std::error::Error>> {
+    let mut keyboard = Keyboard::new(settings.keypresses);
+    let mut renderer = Renderer::new(settings.ansi, settings.theme);
+    let mut history = History::new();
+    let mut message = String::new();
 
     // Game loop
     loop {
-        show_with_message(&game, "")?;
+        renderer.set_moves(history.turns());
+        show_with_message(&renderer, &game, &message)?;
+        message.clear();
 
         // read user input
-        let cmd = loop {
-            match input(BufReader::new(std::io::stdin())) {
-                Some(cmd) => break cmd,
-                None => {
-                    show_with_message(&game, "Invalid command. Please try again.")?;
+        let Some(cmd) = read_command(&renderer, &game, &mut keyboard)? else {
+            break;
+        };
+        match cmd {
+            Command::Undo => {
+                if !history.undo(&mut game) {
+                    message = String::from("Nothing to undo.");
                 }
+                continue;
             }
-        };
-
-        game.theseus_move(cmd);
+            Command::Redo => {
+                if !history.redo(&mut game) {
+                    message = String::from("Nothing to redo.");
+                }
+                continue;
+            }
+            Command::Hint => {
+                message = match game.hint() {
+                    Some(hint) => format!("Hint: {}", hint),
+                    None => String::from("There is no way to win from here. Try undoing a move."),
+                };
+                continue;
+            }
+            Command::Save => {
+                std::fs::write(&settings.save_path, game.save_state())?;
+                message = format!("Game saved to {}.", settings.save_path);
+                continue;
+            }
+            Command::Load if !settings.load => {
+                message = String::from("Loading a saved game isn't available in this mode.");
+                continue;
+            }
+            Command::Load => {
+                let loaded = std::fs::read_to_string(&settings.save_path)
+                    .map_err(|err| err.to_string())
+                    .and_then(|text| Game::load_state(&text).map_err(|err| err.to_string()));
+                message = match loaded {
+                    Ok(loaded) => {
+                        game = loaded;
+                        // the moves so far belong to the game that was replaced
+                        history = History::new();
+                        format!("Game loaded from {}.", settings.save_path)
+                    }
+                    Err(err) => format!("Cannot load {}: {}", settings.save_path, err),
+                };
+                continue;
+            }
+            _ if !game.can_play(cmd) => {
+                message = String::from("Theseus has to move every turn.");
+                continue;
+            }
+            _ => history.record(&game, cmd),
+        }
 
-        if game_over(&game)? {
-            break;
+        renderer.set_moves(history.turns());
+        if take_turn(&renderer, &mut game, cmd)? {
+            if game.status() == GameStatus::Win {
+                break;
+            }
+            // give the player a chance to take back the losing move
+            println!("Enter \"u\" to take back your last move, or anything else to quit.");
+            match keyboard.read() {
+                Ok(Some(Command::Undo)) => {
+                    history.undo(&mut game);
+                }
+                Err(err) if err.kind() != std::io::ErrorKind::Interrupted => return Err(err.into()),
+                _ => break,
+            }
         }
+    }
 
-        show_with_message(&game, "Minotaurs turn 1...")?;
-        wait();
+    Ok((game, history))
+}
 
-        game.minotaur_move();
+fn record(
+    board_path: &str,
+    replay_path: &str,
+    settings: &Settings,
+) -> Result<(), Box<dyn std::error::Error>> {
+    let board = read_board(board_path);
+    let settings = &Settings {
+        load: false,
+        ..settings.clone()
+    };
+    let (game, history) = play(parse_board(board_path, &board, settings.brain), settings)?;
+    let mut replay = Replay::new(&board, history.commands(), game.status());
+    replay.brain = settings.brain.unwrap_or_default();
+    replay.rules = game.rules();
+    std::fs::write(replay_path, replay.to_string())?;
+    println!("Replay saved to {}", replay_path);
+    Ok(())
+}
 
-        if game_over(&game)? {
+fn replay(replay_path: &str, settings: &Settings) -> Result<(), Box<dyn std::error::Error>> {
+    let text = std::fs::read_to_string(replay_path).expect("Cannot find replay file");
+    let replay = Replay::parse(&text)?;
+    // the recorded brain is the one the moves were played against, so it wins over --brain
+    let mut game = replay.start()?;
+    let mut renderer = Renderer::new(settings.ansi, settings.theme);
+    show_with_message(&renderer, &game, "")?;
+    for (i, &cmd) in replay.commands.iter().enumerate() {
+        wait();
+        renderer.set_moves(i + 1);
+        show_with_message(&renderer, &game, &format!("Move {}: {}", i + 1, cmd))?;
+        if take_turn(&renderer, &mut game, cmd)? {
             break;
         }
+    }
+    if game.status() == replay.status {
+        println!(
+            "Replay finished with status {}, as recorded.",
+            game.status()
+        );
+        Ok(())
+    } else {
+        println!(
+            "Replay finished with status {}, but {} was recorded.",
+            game.status(),
+            replay.status
+        );
+        std::process::exit(1);
+    }
+}
 
-        show_with_message(&game, "Minotaurs turn 2...")?;
-        wait();
+// lists the levels in a pack and plays whichever ones the player picks
+fn level_select(pack_path: &str, settings: &Settings) -> Result<(), Box<dyn std::error::Error>> {
+    let levels = load_pack(pack_path, settings.brain)?;
+    let settings = &Settings {
+        load: false,
+        ..settings.clone()
+    };
+    loop {
+        println!("Levels in {}:", pack_path);
+        for (i, level) in levels.iter().enumerate() {
+            let mut line = format!("{:>4}. {}", i + 1, level.title);
+            if let Some(author) = &level.author {
+                line.push_str(&format!(" by {}", author));
+            }
+            if let Some(par) = level.par {
+                line.push_str(&format!(" (par {})", par));
+            }
+            println!("{}", line);
+        }
+        println!("Choose a level by number, or enter \"q\" to quit.");
+
+        let Some(choice) = std::io::stdin().lines().next() else {
+            break;
+        };
+        let choice = choice?;
+        let level = match choice.trim().parse::<usize>() {
+            Ok(n) if n >= 1 && n <= levels.len() => &levels[n - 1],
+            _ if choice.trim() == "q" => break,
+            _ => {
+                println!("Invalid choice. Please try again.");
+                continue;
+            }
+        };
+        let (game, history) = play(level.game.clone(), settings)?;
+        if game.status() == GameStatus::Win {
+            match level.par {
+                Some(par) => println!("Solved in {} moves (par {}).", history.turns(), par),
+                None => println!("Solved in {} moves.", history.turns()),
+            }
+        }
+    }
+    Ok(())
+}
 
-        game.minotaur_move();
+// plays through a pack in order, saving progress after every win so the campaign can be resumed
+fn campaign(
+    pack_path: &str,
+    progress_path: &str,
+    settings: &Settings,
+) -> Result<(), Box<dyn std::error::Error>> {
+    let levels = load_pack(pack_path, settings.brain)?;
+    let settings = &Settings {
+        load: false,
+        ..settings.clone()
+    };
+    let mut progress = match std::fs::read_to_string(progress_path) {
+        Ok(saved) => Progress::parse(&saved)?,
+        Err(_) => Progress::new(),
+    };
+    loop {
+        let next = progress.next_level(&levels);
+        println!("Campaign: {}", pack_path);
+        for (i, level) in levels.iter().enumerate() {
+            let state = match progress.best(&level.title) {
+                Some(best) => format!("best {}", best),
+                None if progress.is_unlocked(&levels, i) => String::from("unlocked"),
+                None => String::from("locked"),
+            };
+            println!("{:>4}. {} ({})", i + 1, level.title, state);
+        }
+        match next {
+            Some(next) => println!(
+                "Choose an unlocked level, press enter to play level {}, or enter \"q\" to quit.",
+                next + 1
+            ),
+            None => {
+                println!("Campaign complete! Choose a level to replay, or enter \"q\" to quit.")
+            }
+        }
 
-        if game_over(&game)? {
+        let Some(choice) = std::io::stdin().lines().next() else {
             break;
+        };
+        let choice = choice?;
+        let index = match (choice.trim(), next) {
+            ("q", _) => break,
+            ("", Some(next)) => next,
+            (choice, _) => match choice.parse::<usize>() {
+                Ok(n) if n >= 1 && n <= levels.len() && progress.is_unlocked(&levels, n - 1) => {
+                    n - 1
+                }
+                _ => {
+                    println!("Invalid choice. Please try again.");
+                    continue;
+                }
+            },
+        };
+        let level = &levels[index];
+        let (game, history) = play(level.game.clone(), settings)?;
+        if game.status() == GameStatus::Win {
+            if progress.record_win(&level.title, history.turns()) {
+                println!("New best: {} moves!", history.turns());
+            }
+            std::fs::write(progress_path, progress.to_string())?;
         }
     }
+    Ok(())
+}
 
+// plays a compact move string without any interaction, reading the moves from stdin if they
+// aren't given, and exits with 0 for a win, 2 for a loss and 3 if the game is still going
+fn run(
+    board_path: &str,
+    moves: Option<&str>,
+    brain: Option<Brain>,
+) -> Result<(), Box<dyn std::error::Error>> {
+    let mut game = load_game(board_path, brain)?;
+    let moves = match moves {
+        Some(moves) => moves.to_string(),
+        None => std::io::read_to_string(std::io::stdin())?,
+    };
+    let status = game.play_moves(&parse_moves(&moves)?);
+    println!("{}", status);
+    std::process::exit(match status {
+        GameStatus::Win => 0,
+        GameStatus::Lose => 2,
+        GameStatus::Continue => 3,
+    });
+}
+
+// lints every board given, printing all the problems found, and exits with 1 if there were any
+fn check(board_paths: &[String]) -> Result<(), Box<dyn std::error::Error>> {
+    let mut failed = 0;
+    for board_path in board_paths {
+        let board = match std::fs::read_to_string(board_path) {
+            Ok(board) => board,
+            Err(err) => {
+                eprintln!("error: cannot read {}: {}", board_path, err);
+                failed += 1;
+                continue;
+            }
+        };
+        // level packs are checked level by level, with each level's problems under its title
+        let levels = if board.trim_start().starts_with('@') {
+            match check_pack(&board) {
+                Ok(levels) => levels,
+                Err(err) => {
+                    eprintln!("error: {}\n --> {}\n", err, board_path);
+                    failed += 1;
+                    continue;
+                }
+            }
+        } else {
+            vec![(String::new(), check_board(&board))]
+        };
+        for (i, (title, problems)) in levels.iter().enumerate() {
+            if !problems.is_empty() && !title.is_empty() {
+                eprintln!("In level {} ({}):", i + 1, title);
+            }
+            for problem in problems {
+                eprintln!("{}", problem.render(board_path, &board));
+            }
+        }
+        if levels.iter().any(|(_, problems)| !problems.is_empty()) {
+            failed += 1;
+        }
+    }
+    if failed > 0 {
+        eprintln!(
+            "{} of {} boards failed the check.",
+            failed,
+            board_paths.len()
+        );
+        std::process::exit(1);
+    }
+    println!("All {} boards passed the check.", board_paths.len());
     Ok(())
 }
+
+// looks up the value following `flag`, exiting with the usage message if it isn't a number
+fn numeric_flag<T: std::str::FromStr>(args: &[String], flag: &str) -> Option<T> {
+    let i = args.iter().position(|arg| arg == flag)?;
+    match args.get(i + 1).map(|value| value.parse()) {
+        Some(Ok(value)) => Some(value),
+        _ => usage(),
+    }
+}
+
+fn generate_board(args: &[String]) -> Result<(), Box<dyn std::error::Error>> {
+    let (Some(width), Some(height)) = (
+        numeric_flag(args, "--width"),
+        numeric_flag(args, "--height"),
+    ) else {
+        usage()
+    };
+    let mut options = GeneratorOptions::new(width, height);
+    options.seed = numeric_flag(args, "--seed").unwrap_or_else(|| {
+        std::time::SystemTime::now()
+            .duration_since(std::time::UNIX_EPOCH)
+            .map(|time| time.as_secs())
+            .unwrap_or(0)
+    });
+    if let Some(min_moves) = numeric_flag(args, "--min-moves") {
+        options.min_moves = min_moves;
+    }
+    print!("{}", generate(&options)?);
+    Ok(())
+}
+
+fn main() -> Result<(), Box<dyn std::error::Error>> {
+    // Read command line arguments
+    let mut args: Vec<String> = std::env::args().skip(1).collect();
+    let progress_path = take_option(&mut args, "--progress");
+    let save_path =
+        take_option(&mut args, "--save").unwrap_or_else(|| String::from("theseus.save"));
+    let theme = match take_option(&mut args, "--theme") {
+        Some(name) => Theme::by_name(&name).unwrap_or_else(|| usage()),
+        None => Theme::classic(),
+    };
+    let brain = take_option(&mut args, "--brain")
+        .map(|name| Brain::by_name(&name).unwrap_or_else(|| usage()));
+    let settings = Settings {
+        keypresses: !take_flag(&mut args, "--line-input"),
+        ansi: !take_flag(&mut args, "--no-ansi"),
+        theme,
+        save_path,
+        brain,
+        load: true,
+    };
+    match args.as_slice() {
+        [cmd, board_path] if cmd == "solve" => solve(board_path, brain),
+        [cmd, board_path, replay_path] if cmd == "record" => {
+            record(board_path, replay_path, &settings)
+        }
+        [cmd, replay_path] if cmd == "replay" => replay(replay_path, &settings),
+        [cmd, pack_path] if cmd == "pack" => level_select(pack_path, &settings),
+        [cmd, pack_path] if cmd == "campaign" => {
+            let progress_path = progress_path.unwrap_or_else(|| format!("{}.progress", pack_path));
+            campaign(pack_path, &progress_path, &settings)
+        }
+        [cmd, flags @ ..] if cmd == "generate" => generate_board(flags),
+        [cmd, board_paths @ ..] if cmd == "check" && !board_paths.is_empty() => check(board_paths),
+        [cmd, board_path] if cmd == "run" => run(board_path, None, brain),
+        [cmd, board_path, moves] if cmd == "run" => run(board_path, Some(moves), brain),
+        [board_path] => {
+            play(load_game(board_path, brain)?, &settings)?;
+            Ok(())
+        }
+        _ => usage(),
+    }
+}
@@ -0,0 +1,222 @@
+use std::error::Error;
+use std::fmt::Display;
+
+use crate::{parse_moves, BoardError, Brain, Command, Game, InvalidMove};
+
+/// A single board from a level pack, along with its metadata
+#[derive(Clone)]
+pub struct Level {
+    pub title: String,
+    pub author: Option<String>,
+    /// The number of moves a good solution takes
+    pub par: Option<usize>,
+    pub solution: Option<Vec<Command>>,
+    pub board: String,
+    pub game: Game,
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub enum PackErrorKind {
+    /// Something other than a blank line came before the first `@level` header
+    TextBeforeLevel,
+    UnknownHeader(String),
+    InvalidPar(String),
+    UnknownBrain(String),
+    InvalidSolution(InvalidMove),
+    Board(BoardError),
+    NoLevels,
+}
+impl Display for PackErrorKind {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            PackErrorKind::TextBeforeLevel => write!(f, "Text before the first @level header"),
+            PackErrorKind::UnknownHeader(header) => write!(f, "Unknown header: @{}", header),
+            PackErrorKind::InvalidPar(par) => write!(f, "Invalid par: {}", par),
+            PackErrorKind::UnknownBrain(name) => write!(f, "Unknown Minotaur brain: {}", name),
+            PackErrorKind::InvalidSolution(err) => write!(f, "Invalid solution: {}", err),
+            PackErrorKind::Board(err) => write!(f, "Invalid board: {}", err),
+            PackErrorKind::NoLevels => write!(f, "No levels"),
+        }
+    }
+}
+
+/// An error in a level pack, along with which level it's in
+#[derive(Clone, Debug, PartialEq)]
+pub struct PackError {
+    /// The number of the broken level, counting from 1, or 0 if the error isn't in a level
+    pub level: usize,
+    pub title: String,
+    pub kind: PackErrorKind,
+}
+impl Display for PackError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        if self.level == 0 {
+            write!(f, "{}", self.kind)
+        } else {
+            write!(f, "Level {} ({}): {}", self.level, self.title, self.kind)
+        }
+    }
+}
+impl Error for PackError {}
+
+// a level whose headers have been read but whose board hasn't been parsed yet
+pub(crate) struct Draft {
+    pub(crate) title: String,
+    author: Option<String>,
+    par: Option<usize>,
+    solution: Option<Vec<Command>>,
+    pub(crate) brain: Brain,
+    lines: Vec<String>,
+    // the number of lines in the pack before the board starts
+    first_line: usize,
+}
+
+impl Draft {
+    // returns the text of the board along with the number of lines in the pack before it
+    pub(crate) fn board(&self) -> (String, usize) {
+        let mut lines = &self.lines[..];
+        // blank lines between levels aren't part of the board
+        while lines.last().is_some_and(|line| line.trim().is_empty()) {
+            lines = &lines[..lines.len() - 1];
+        }
+        let board = lines.iter().map(|line| format!("{}\n", line)).collect();
+        (board, self.first_line)
+    }
+
+    fn finish(self) -> Result<Level, PackErrorKind> {
+        let (board, first_line) = self.board();
+        // errors point at lines of the pack rather than of the board on its own
+        let mut game = Game::parse(&board)
+            .map_err(|err| PackErrorKind::Board(err.offset_lines(first_line)))?;
+        game.set_brain(self.brain);
+        Ok(Level {
+            title: self.title,
+            author: self.author,
+            par: self.par,
+            solution: self.solution,
+            board,
+            game,
+        })
+    }
+}
+
+/// Parses a level pack: a file with many boards, each starting with a run of header lines.
+/// ```text
+/// @level First Steps
+/// @author Robert Abbott
+/// @par 10
+/// @solution aaddddssdd
+/// @brain classic
+/// XXXXXXXXX
+/// X  T  XXX
+/// ...
+/// @level The Next One
+/// ...
+/// ```
+/// Only `@level` is required. `@solution` is a compact move string like `parse_moves` reads.
+/// `@brain` names the `Brain` the level's Minotaurs use, which is `classic` if it's left out.
+/// Either board format can be used, and blank lines before and after a board are ignored. The
+/// locations in board errors are lines of the whole pack.
+pub fn parse_pack(text: &str) -> Result<Vec<Level>, PackError> {
+    let drafts = read_drafts(text)?;
+    let numbers = 1..=drafts.len();
+    drafts
+        .into_iter()
+        .zip(numbers)
+        .map(|(draft, number)| finish(draft, number))
+        .collect()
+}
+
+// splits a pack into its levels and reads their headers, leaving the boards to be parsed
+pub(crate) fn read_drafts(text: &str) -> Result<Vec<Draft>, PackError> {
+    let mut drafts = Vec::new();
+    let mut draft: Option<Draft> = None;
+    for (i, line) in text.lines().enumerate() {
+        let Some(header) = line.strip_prefix('@') else {
+            match draft.as_mut() {
+                // blank lines before the board, like those after it, aren't part of it
+                Some(draft) if draft.lines.is_empty() && line.trim().is_empty() => {}
+                Some(draft) => {
+                    if draft.lines.is_empty() {
+                        draft.first_line = i;
+                    }
+                    draft.lines.push(line.to_string());
+                }
+                None if line.trim().is_empty() => {}
+                None => {
+                    return Err(PackError {
+                        level: 0,
+                        title: String::new(),
+                        kind: PackErrorKind::TextBeforeLevel,
+                    })
+                }
+            }
+            continue;
+        };
+        let (name, value) = header.split_once(' ').unwrap_or((header, ""));
+        let value = value.trim();
+        if name == "level" {
+            drafts.extend(draft.take());
+            draft = Some(Draft {
+                title: value.to_string(),
+                author: None,
+                par: None,
+                solution: None,
+                brain: Brain::Classic,
+                lines: Vec::new(),
+                first_line: i + 1,
+            });
+            continue;
+        }
+        let Some(current) = draft.as_mut() else {
+            return Err(PackError {
+                level: 0,
+                title: String::new(),
+                kind: PackErrorKind::TextBeforeLevel,
+            });
+        };
+        let err = |kind| PackError {
+            level: drafts.len() + 1,
+            title: current.title.clone(),
+            kind,
+        };
+        match name {
+            "author" => current.author = Some(value.to_string()),
+            "par" => {
+                let par = value.parse();
+                current.par = Some(par.map_err(|_| err(PackErrorKind::InvalidPar(value.into())))?);
+            }
+            "brain" => {
+                let brain = Brain::by_name(value);
+                current.brain =
+                    brain.ok_or_else(|| err(PackErrorKind::UnknownBrain(value.into())))?;
+            }
+            "solution" => {
+                let moves = parse_moves(value);
+                current.solution = Some(moves.map_err(|e| err(PackErrorKind::InvalidSolution(e)))?);
+            }
+            other => return Err(err(PackErrorKind::UnknownHeader(other.to_string()))),
+        }
+    }
+    match draft {
+        Some(done) => drafts.push(done),
+        None => {
+            return Err(PackError {
+                level: 0,
+                title: String::new(),
+                kind: PackErrorKind::NoLevels,
+            })
+        }
+    }
+    Ok(drafts)
+}
+
+// turns a draft into a level, labelling any error with the level's number
+fn finish(draft: Draft, number: usize) -> Result<Level, PackError> {
+    let title = draft.title.clone();
+    draft.finish().map_err(|kind| PackError {
+        level: number,
+        title,
+        kind,
+    })
+}
@@ -0,0 +1,114 @@
+use crate::Command;
+
+/// A cell on the board, counting rows down and columns across from 0 at the top left. This is the
+/// same order the `is_*` functions on `Game` take their arguments in.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct Position {
+    pub row: usize,
+    pub col: usize,
+}
+
+impl Position {
+    pub fn new(row: usize, col: usize) -> Position {
+        Position { row, col }
+    }
+
+    /// Returns the position one step away in `direction`, or None if that would go above the top
+    /// row or left of the first column
+    pub fn step(self, direction: Direction) -> Option<Position> {
+        let Position { row, col } = self;
+        match direction {
+            Direction::Up => Some(Position::new(row.checked_sub(1)?, col)),
+            Direction::Down => Some(Position::new(row + 1, col)),
+            Direction::Left => Some(Position::new(row, col.checked_sub(1)?)),
+            Direction::Right => Some(Position::new(row, col + 1)),
+        }
+    }
+
+    /// Returns the positions one step away in each direction, in the order of `Direction::ALL`,
+    /// leaving out any above the top row or left of the first column
+    pub fn neighbors(self) -> impl Iterator<Item = Position> {
+        Direction::ALL
+            .into_iter()
+            .filter_map(move |direction| self.step(direction))
+    }
+
+    /// Returns true if the position is inside a board `width` columns wide and `height` rows tall
+    pub fn is_within(self, width: usize, height: usize) -> bool {
+        self.row < height && self.col < width
+    }
+
+    /// Like `step`, but also returns None if the step leaves a board `width` columns wide and
+    /// `height` rows tall
+    pub fn step_within(
+        self,
+        direction: Direction,
+        width: usize,
+        height: usize,
+    ) -> Option<Position> {
+        self.step(direction)
+            .filter(|next| next.is_within(width, height))
+    }
+
+    /// Like `neighbors`, but only the ones inside a board `width` columns wide and `height` rows
+    /// tall
+    pub fn neighbors_within(self, width: usize, height: usize) -> impl Iterator<Item = Position> {
+        self.neighbors()
+            .filter(move |next| next.is_within(width, height))
+    }
+}
+
+/// A single step on the board
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum Direction {
+    Up,
+    Down,
+    Left,
+    Right,
+}
+
+impl Direction {
+    /// Every direction, in the order Theseus's moves are tried by the solver
+    pub const ALL: [Direction; 4] = [
+        Direction::Up,
+        Direction::Down,
+        Direction::Left,
+        Direction::Right,
+    ];
+
+    pub fn opposite(self) -> Direction {
+        match self {
+            Direction::Up => Direction::Down,
+            Direction::Down => Direction::Up,
+            Direction::Left => Direction::Right,
+            Direction::Right => Direction::Left,
+        }
+    }
+}
+
+impl From<Direction> for Command {
+    fn from(direction: Direction) -> Command {
+        match direction {
+            Direction::Up => Command::Up,
+            Direction::Down => Command::Down,
+            Direction::Left => Command::Left,
+            Direction::Right => Command::Right,
+        }
+    }
+}
+
+/// Only the commands that move Theseus have a direction. Any other command is handed back as the
+/// error.
+impl TryFrom<Command> for Direction {
+    type Error = Command;
+
+    fn try_from(command: Command) -> Result<Direction, Command> {
+        match command {
+            Command::Up => Ok(Direction::Up),
+            Command::Down => Ok(Direction::Down),
+            Command::Left => Ok(Direction::Left),
+            Command::Right => Ok(Direction::Right),
+            other => Err(other),
+        }
+    }
+}
@@ -0,0 +1,133 @@
+use std::io::{self, IsTerminal, Write};
+
+use crate::Game;
+
+// moves the cursor to the top left corner of the terminal
+const HOME: &str = "\x1b[H";
+// clears from the cursor to the end of the line
+const CLEAR_LINE: &str = "\x1b[K";
+// clears from the cursor to the end of the screen
+const CLEAR_BELOW: &str = "\x1b[J";
+
+/// ANSI colors for each kind of glyph on the board, as SGR parameters like `"1;34"`. `None`
+/// leaves that glyph uncolored.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Theme {
+    pub theseus: Option<&'static str>,
+    pub minotaur: Option<&'static str>,
+    pub goal: Option<&'static str>,
+    pub wall: Option<&'static str>,
+}
+
+impl Theme {
+    /// No colors at all
+    pub fn plain() -> Theme {
+        Theme {
+            theseus: None,
+            minotaur: None,
+            goal: None,
+            wall: None,
+        }
+    }
+
+    /// Theseus in blue and the Minotaur in red, like the dots in the original puzzle
+    pub fn classic() -> Theme {
+        Theme {
+            theseus: Some("1;34"),
+            minotaur: Some("1;31"),
+            goal: Some("1;32"),
+            wall: Some("2"),
+        }
+    }
+
+    /// Bright pieces on solid backgrounds, with plain white walls
+    pub fn high_contrast() -> Theme {
+        Theme {
+            theseus: Some("1;97;44"),
+            minotaur: Some("1;97;41"),
+            goal: Some("1;30;103"),
+            wall: Some("97"),
+        }
+    }
+
+    /// Blue, orange and yellow, which stay distinct under the common forms of color blindness
+    pub fn colorblind() -> Theme {
+        Theme {
+            theseus: Some("1;38;5;33"),
+            minotaur: Some("1;38;5;208"),
+            goal: Some("1;38;5;226"),
+            wall: Some("38;5;245"),
+        }
+    }
+
+    /// Looks up a theme by the name used on the command line
+    pub fn by_name(name: &str) -> Option<Theme> {
+        match name {
+            "plain" => Some(Theme::plain()),
+            "classic" => Some(Theme::classic()),
+            "high-contrast" => Some(Theme::high_contrast()),
+            "colorblind" => Some(Theme::colorblind()),
+            _ => None,
+        }
+    }
+
+    // adds `c` to `line`, wrapped in the escapes for `color` if there is one
+    pub(crate) fn paint(line: &mut String, c: char, color: Option<&str>) {
+        match color {
+            Some(color) => {
+                line.push_str(&format!("\x1b[{}m{}\x1b[0m", color, c));
+            }
+            None => line.push(c),
+        }
+    }
+}
+
+/// Draws the board for the interactive modes, followed by a status line with the move count and
+/// a message. With ANSI escapes each frame is drawn over the last one; without them, frames are
+/// printed one after another.
+pub struct Renderer {
+    ansi: bool,
+    theme: Theme,
+    moves: usize,
+}
+
+impl Renderer {
+    /// Creates a renderer, only using ANSI escapes, including the colors from `theme`, if `ansi`
+    /// is true and stdout is a terminal
+    pub fn new(ansi: bool, theme: Theme) -> Renderer {
+        let ansi = ansi && io::stdout().is_terminal();
+        if ansi {
+            // start from a blank screen so old output doesn't show around the board
+            print!("{}\x1b[2J", HOME);
+        }
+        let theme = if ansi { theme } else { Theme::plain() };
+        Renderer {
+            ansi,
+            theme,
+            moves: 0,
+        }
+    }
+
+    /// Sets the move count shown in the status line
+    pub fn set_moves(&mut self, moves: usize) {
+        self.moves = moves;
+    }
+
+    /// Writes one frame into `out`, which is normally stdout
+    pub fn draw(&self, out: &mut impl Write, game: &Game, message: &str) -> io::Result<()> {
+        if self.ansi {
+            write!(out, "{}", HOME)?;
+        }
+        game.render_to(out, &self.theme)?;
+        if self.ansi {
+            writeln!(out, "{}Moves: {}", CLEAR_LINE, self.moves)?;
+            writeln!(out, "{}{}", CLEAR_LINE, message)?;
+            write!(out, "{}", CLEAR_BELOW)?;
+        } else {
+            writeln!(out, "Moves: {}", self.moves)?;
+            writeln!(out, "{}", message)?;
+        }
+        // the prompt may be waiting on a keypress, so make sure the frame shows up first
+        out.flush()
+    }
+}
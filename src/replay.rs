@@ -0,0 +1,176 @@
+use std::error::Error;
+use std::fmt::Display;
+
+use crate::{parse_command, BoardError, Brain, Command, Game, GameStatus, MinotaurBrain, Ruleset};
+
+/// A recorded playthrough: the board it was played on, the commands entered and the status the
+/// game finished with, along with the brain and rules it was played by.
+///
+/// Replays are stored as text, with a few `key: value` header lines followed by the board. A
+/// `brain` line is only written for brains other than the classic one, and each rule that differs
+/// from `Ruleset::default()` gets a line of its own, the same as in save files.
+/// ```text
+/// status: win
+/// moves: left left right down
+/// brain: hunter
+/// minotaur-steps 3
+/// board:
+/// XXXXXXXXX
+/// X  T  XXX
+/// ...
+/// ```
+#[derive(Clone, Debug, PartialEq)]
+pub struct Replay {
+    pub board: String,
+    pub commands: Vec<Command>,
+    pub status: GameStatus,
+    pub brain: Brain,
+    pub rules: Ruleset,
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub enum ReplayError {
+    UnknownField(String),
+    InvalidStatus(String),
+    InvalidMove(String),
+    UnknownBrain(String),
+    MissingStatus,
+    MissingBoard,
+    Board(BoardError),
+}
+impl Display for ReplayError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ReplayError::UnknownField(field) => write!(f, "Unknown field: {}", field),
+            ReplayError::InvalidStatus(status) => write!(f, "Invalid status: {}", status),
+            ReplayError::InvalidMove(word) => write!(f, "Invalid move: {}", word),
+            ReplayError::UnknownBrain(name) => write!(f, "Unknown Minotaur brain: {}", name),
+            ReplayError::MissingStatus => write!(f, "Missing status"),
+            ReplayError::MissingBoard => write!(f, "Missing board"),
+            ReplayError::Board(err) => write!(f, "Invalid board: {}", err),
+        }
+    }
+}
+impl Error for ReplayError {}
+impl From<BoardError> for ReplayError {
+    fn from(err: BoardError) -> Self {
+        ReplayError::Board(err)
+    }
+}
+
+fn parse_status(word: &str) -> Option<GameStatus> {
+    match word {
+        "win" => Some(GameStatus::Win),
+        "lose" => Some(GameStatus::Lose),
+        "continue" => Some(GameStatus::Continue),
+        _ => None,
+    }
+}
+
+impl Replay {
+    /// Creates a replay played with the classic brain and the default rules
+    pub fn new(board: &str, commands: Vec<Command>, status: GameStatus) -> Replay {
+        Replay {
+            board: board.to_string(),
+            commands,
+            status,
+            brain: Brain::Classic,
+            rules: Ruleset::default(),
+        }
+    }
+
+    /// Parses a replay from its text format. The board is checked to be a valid board, but the
+    /// moves are not played out; use `Replay::play` for that.
+    pub fn parse(text: &str) -> Result<Replay, ReplayError> {
+        let mut status = None;
+        let mut commands = Vec::new();
+        let mut brain = Brain::Classic;
+        let mut rules = Ruleset::default();
+        let mut lines = text.lines();
+        // header lines run until the board starts
+        for line in lines.by_ref() {
+            if rules.read_line(line) {
+                continue;
+            }
+            let (key, value) = line.split_once(':').unwrap_or((line, ""));
+            match key.trim() {
+                "status" => {
+                    let value = value.trim();
+                    status = Some(
+                        parse_status(value)
+                            .ok_or_else(|| ReplayError::InvalidStatus(value.to_string()))?,
+                    );
+                }
+                "moves" => {
+                    for word in value.split_whitespace() {
+                        match parse_command(word) {
+                            Some(
+                                cmd @ (Command::Up
+                                | Command::Down
+                                | Command::Left
+                                | Command::Right
+                                | Command::Skip),
+                            ) => commands.push(cmd),
+                            _ => return Err(ReplayError::InvalidMove(word.to_string())),
+                        }
+                    }
+                }
+                "brain" => {
+                    let value = value.trim();
+                    brain = Brain::by_name(value)
+                        .ok_or_else(|| ReplayError::UnknownBrain(value.to_string()))?;
+                }
+                "board" => {
+                    let board: Vec<&str> = lines.collect();
+                    let board = board.join("\n") + "\n";
+                    Game::parse(&board)?;
+                    return Ok(Replay {
+                        board,
+                        commands,
+                        status: status.ok_or(ReplayError::MissingStatus)?,
+                        brain,
+                        rules,
+                    });
+                }
+                "" => {}
+                other => return Err(ReplayError::UnknownField(other.to_string())),
+            }
+        }
+        Err(ReplayError::MissingBoard)
+    }
+
+    /// Returns the game as it was before the first move, with the recorded brain and rules
+    pub fn start(&self) -> Result<Game, BoardError> {
+        let mut game = Game::parse(&self.board)?;
+        game.set_brain(self.brain);
+        game.set_rules(self.rules);
+        Ok(game)
+    }
+
+    /// Plays the recorded commands from the start of the board, stopping early if the game ends,
+    /// and returns the final game
+    pub fn play(&self) -> Result<Game, BoardError> {
+        let mut game = self.start()?;
+        game.play_moves(&self.commands);
+        Ok(game)
+    }
+}
+
+impl Display for Replay {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        writeln!(f, "status: {}", self.status)?;
+        let moves: Vec<String> = self.commands.iter().map(|cmd| cmd.to_string()).collect();
+        writeln!(f, "moves: {}", moves.join(" "))?;
+        if self.brain != Brain::Classic {
+            writeln!(f, "brain: {}", self.brain.name())?;
+        }
+        for line in self.rules.to_lines() {
+            writeln!(f, "{}", line)?;
+        }
+        writeln!(f, "board:")?;
+        for line in self.board.lines() {
+            writeln!(f, "{}", line)?;
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,68 @@
+/// The rules a game is played by. `Ruleset::default()` gives the rules of the original puzzle.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Ruleset {
+    /// How many steps each Minotaur takes per turn
+    pub minotaur_steps: usize,
+    /// The Minotaurs take their steps before Theseus moves, rather than after
+    pub minotaurs_first: bool,
+    /// Theseus may stay where he is for a turn, by skipping or walking into a wall
+    pub allow_skip: bool,
+    /// Minotaurs may step onto the goal. When they can't, it's a wall to them.
+    pub minotaurs_enter_goal: bool,
+    /// Theseus is caught as soon as a Minotaur reaches him, even partway through a turn. When
+    /// this is off, he's only caught if a Minotaur is standing on him once the turn is over, so
+    /// one can pass through him.
+    pub catch_mid_turn: bool,
+}
+
+impl Default for Ruleset {
+    fn default() -> Ruleset {
+        Ruleset {
+            minotaur_steps: 2,
+            minotaurs_first: false,
+            allow_skip: true,
+            minotaurs_enter_goal: true,
+            catch_mid_turn: true,
+        }
+    }
+}
+
+impl Ruleset {
+    // the lines save files and replays write for each rule that differs from the default ones
+    pub(crate) fn to_lines(self) -> Vec<String> {
+        let default = Ruleset::default();
+        let mut lines = Vec::new();
+        if self.minotaur_steps != default.minotaur_steps {
+            lines.push(format!("minotaur-steps {}", self.minotaur_steps));
+        }
+        let flags = [
+            (self.minotaurs_first, "minotaurs-first"),
+            (!self.allow_skip, "no-skip"),
+            (!self.minotaurs_enter_goal, "minotaurs-avoid-goal"),
+            (!self.catch_mid_turn, "catch-at-end"),
+        ];
+        for (set, flag) in flags {
+            if set {
+                lines.push(flag.to_string());
+            }
+        }
+        lines
+    }
+
+    // reads one of the lines written by `to_lines` into the rules, returning false if it isn't one
+    pub(crate) fn read_line(&mut self, line: &str) -> bool {
+        let words: Vec<&str> = line.split_whitespace().collect();
+        match words[..] {
+            ["minotaur-steps", steps] => match steps.parse() {
+                Ok(steps) => self.minotaur_steps = steps,
+                Err(_) => return false,
+            },
+            ["minotaurs-first"] => self.minotaurs_first = true,
+            ["no-skip"] => self.allow_skip = false,
+            ["minotaurs-avoid-goal"] => self.minotaurs_enter_goal = false,
+            ["catch-at-end"] => self.catch_mid_turn = false,
+            _ => return false,
+        }
+        true
+    }
+}
@@ -0,0 +1,144 @@
+use std::error::Error;
+use std::fmt::Display;
+
+use crate::{BoardError, Brain, Cell, Game, Layout, MinotaurBrain, Pieces, Position, Ruleset};
+
+#[derive(Clone, Debug, PartialEq)]
+pub enum SaveError {
+    /// A header line that isn't a known piece, or has the wrong number of coordinates
+    InvalidLine(String),
+    /// A piece placed in a wall or off the board
+    InvalidPosition(Position),
+    MissingBoard,
+    Board(BoardError),
+}
+impl Display for SaveError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            SaveError::InvalidLine(line) => write!(f, "Invalid line: {}", line),
+            SaveError::InvalidPosition(pos) => {
+                write!(f, "Invalid position: row {}, column {}", pos.row, pos.col)
+            }
+            SaveError::MissingBoard => write!(f, "Missing board"),
+            SaveError::Board(err) => write!(f, "Invalid board: {}", err),
+        }
+    }
+}
+impl Error for SaveError {}
+impl From<BoardError> for SaveError {
+    fn from(err: BoardError) -> Self {
+        SaveError::Board(err)
+    }
+}
+
+// Saved games list where each piece is, as a row and column, and then the walls in the same
+// format as the board they came from. The pieces can't be drawn on the board itself, since
+// once they move they can end up sharing a cell, like a Minotaur standing on the goal. Boards
+// won by an exit may have no goal line, and a game Theseus has escaped from has an `escaped` line.
+// Games where the Minotaurs don't use the classic brain have a `brain` line naming it, and each
+// rule that differs from `Ruleset::default()` gets a line of its own.
+// ```
+// theseus 1 3
+// minotaur 5 3
+// goal 3 7
+// board:
+// XXXXXXXXX
+// X     XXX
+// ...
+// ```
+pub(crate) fn save(game: &Game) -> String {
+    let mut text = String::new();
+    let mut line = |name: &str, pos: Position| {
+        text.push_str(&format!("{} {} {}\n", name, pos.row, pos.col));
+    };
+    line("theseus", game.theseus);
+    for &minotaur in &game.minotaurs {
+        line("minotaur", minotaur);
+    }
+    if let Some(goal) = game.goal {
+        line("goal", goal);
+    }
+    if game.escaped {
+        text.push_str("escaped\n");
+    }
+    if game.brain.name() != Brain::Classic.name() {
+        text.push_str(&format!("brain {}\n", game.brain.name()));
+    }
+    for line in game.rules.to_lines() {
+        text.push_str(&format!("{}\n", line));
+    }
+    text.push_str("board:\n");
+    text.push_str(&game.layout.to_text());
+    text
+}
+
+pub(crate) fn load(text: &str) -> Result<Game, SaveError> {
+    let mut pieces = Pieces::default();
+    let mut escaped = false;
+    let mut brain = None;
+    let mut rules = Ruleset::default();
+    let mut lines = text.lines();
+    // the list of pieces runs until the board starts
+    for line in lines.by_ref() {
+        if line == "board:" {
+            let board: String = lines.map(|line| format!("{}\n", line)).collect();
+            let (layout, found) = Layout::parse(&board)?;
+            // the pieces all come from the list, so the board itself should have none
+            if found.theseus.is_some() || found.goal.is_some() || !found.minotaurs.is_empty() {
+                return Err(SaveError::InvalidLine(String::from("board:")));
+            }
+            let mut game = pieces.into_game(layout)?;
+            game.escaped = escaped;
+            game.set_brain(brain.unwrap_or_default());
+            game.rules = rules;
+            return check_positions(game);
+        }
+        if rules.read_line(line) {
+            continue;
+        }
+        let words: Vec<&str> = line.split_whitespace().collect();
+        let pos = match words[..] {
+            [_, row, col] => match (row.parse(), col.parse()) {
+                (Ok(row), Ok(col)) => Position::new(row, col),
+                _ => return Err(SaveError::InvalidLine(line.to_string())),
+            },
+            [] => continue,
+            ["escaped"] if !escaped => {
+                escaped = true;
+                continue;
+            }
+            ["brain", name] if brain.is_none() => match Brain::by_name(name) {
+                Some(found) => {
+                    brain = Some(found);
+                    continue;
+                }
+                None => return Err(SaveError::InvalidLine(line.to_string())),
+            },
+            _ => return Err(SaveError::InvalidLine(line.to_string())),
+        };
+        match words[0] {
+            "theseus" if pieces.theseus.is_none() => pieces.theseus = Some(pos),
+            "minotaur" => pieces.minotaurs.push(pos),
+            "goal" if pieces.goal.is_none() => pieces.goal = Some(pos),
+            _ => return Err(SaveError::InvalidLine(line.to_string())),
+        }
+    }
+    Err(SaveError::MissingBoard)
+}
+
+// makes sure every piece is standing on an open cell of the board
+fn check_positions(game: Game) -> Result<Game, SaveError> {
+    let mut positions = vec![game.theseus];
+    positions.extend(&game.minotaurs);
+    positions.extend(game.goal);
+    for pos in positions {
+        let open = match &game.layout {
+            Layout::Cells(grid) => grid.get(pos) == Some(Cell::Open),
+            Layout::Edges(edges) => edges.walls(pos.row, pos.col).is_some(),
+        };
+        if !open {
+            return Err(SaveError::InvalidPosition(pos));
+        }
+    }
+    Ok(game)
+}
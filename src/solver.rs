@@ -0,0 +1,75 @@
+use std::collections::{HashMap, VecDeque};
+use std::error::Error;
+use std::fmt::Display;
+
+use crate::{Command, Game, GameStatus, Position};
+
+/// Returned by `Game::solve` when no sequence of moves reaches `GameStatus::Win`
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Unsolvable;
+impl Display for Unsolvable {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "Board is unsolvable")
+    }
+}
+impl Error for Unsolvable {}
+
+// every command Theseus can enter, in the order they're tried
+const MOVES: [Command; 5] = [
+    Command::Up,
+    Command::Down,
+    Command::Left,
+    Command::Right,
+    Command::Skip,
+];
+
+// the part of a game that changes from turn to turn
+type State = (Position, Vec<Position>, bool);
+
+fn state(game: &Game) -> State {
+    (game.theseus, game.minotaurs.clone(), game.escaped)
+}
+
+/// Searches every reachable position of Theseus and the Minotaurs breadth-first and
+/// returns the shortest list of commands that wins the game
+pub fn solve(game: &Game) -> Result<Vec<Command>, Unsolvable> {
+    match game.status() {
+        GameStatus::Win => return Ok(Vec::new()),
+        GameStatus::Lose => return Err(Unsolvable),
+        GameStatus::Continue => {}
+    }
+    // maps each visited state to the state and command that first reached it
+    let mut parents: HashMap<State, Option<(State, Command)>> = HashMap::new();
+    let mut queue = VecDeque::new();
+    parents.insert(state(game), None);
+    queue.push_back(game.clone());
+    while let Some(current) = queue.pop_front() {
+        for command in MOVES {
+            let mut next = current.clone();
+            next.play_turn(command);
+            let key = state(&next);
+            if parents.contains_key(&key) {
+                continue;
+            }
+            parents.insert(key.clone(), Some((state(&current), command)));
+            match next.status() {
+                GameStatus::Win => return Ok(path_to(&parents, key)),
+                GameStatus::Lose => {}
+                GameStatus::Continue => queue.push_back(next),
+            }
+        }
+    }
+    Err(Unsolvable)
+}
+
+// walks back through the parent links to recover the commands leading to `end`
+fn path_to(parents: &HashMap<State, Option<(State, Command)>>, end: State) -> Vec<Command> {
+    let mut commands = Vec::new();
+    let mut current = end;
+    while let Some(Some((previous, command))) = parents.get(&current) {
+        commands.push(*command);
+        current = previous.clone();
+    }
+    commands.reverse();
+    commands
+}
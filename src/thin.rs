@@ -0,0 +1,211 @@
+use crate::{check_and_set, BoardError, Direction, Game, Layout, Location, Pieces, Position};
+
+/// The walls on each side of a single cell of a thin-wall board
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub struct Walls {
+    pub north: bool,
+    pub east: bool,
+    pub south: bool,
+    pub west: bool,
+}
+
+impl Walls {
+    fn blocks(&self, direction: Direction) -> bool {
+        match direction {
+            Direction::Up => self.north,
+            Direction::Right => self.east,
+            Direction::Down => self.south,
+            Direction::Left => self.west,
+        }
+    }
+}
+
+/// A board whose walls sit on the edges between cells, like the original puzzle, rather than
+/// filling whole cells like `Grid`
+#[derive(Clone, Debug, PartialEq)]
+pub struct EdgeGrid {
+    cells: Vec<Vec<Walls>>, // indexed by row, then column
+    // gaps in the outer wall that Theseus can escape through, as the cell next to the gap and the
+    // direction out of it
+    pub(crate) exits: Vec<(Position, Direction)>,
+}
+
+impl EdgeGrid {
+    pub fn width(&self) -> usize {
+        self.cells.first().map_or(0, |row| row.len())
+    }
+
+    pub fn height(&self) -> usize {
+        self.cells.len()
+    }
+
+    /// Returns the walls around the cell at the given position, or None if it's off the board
+    pub fn walls(&self, row: usize, col: usize) -> Option<Walls> {
+        self.cells.get(row)?.get(col).copied()
+    }
+
+    // returns the position one step from `pos` in `direction`, or None if a wall or the edge of
+    // the board is in the way
+    pub(crate) fn step(&self, pos: Position, direction: Direction) -> Option<Position> {
+        if self.walls(pos.row, pos.col)?.blocks(direction) {
+            return None;
+        }
+        let next = pos.step(direction)?;
+        self.walls(next.row, next.col).map(|_| next)
+    }
+
+    // writes out the walls and exits in the thin-wall format, with no pieces on the board
+    pub(crate) fn to_text(&self) -> String {
+        let exits: Vec<Position> = self
+            .exits
+            .iter()
+            .filter_map(|&(pos, direction)| expanded(pos).step(direction))
+            .collect();
+        let mut text = String::new();
+        for (y, row) in self.expand().iter().enumerate() {
+            for (x, &wall) in row.iter().enumerate() {
+                text.push(match (x % 2 == 1, y % 2 == 1) {
+                    (false, false) => '+',
+                    _ if exits.contains(&Position::new(y, x)) => 'E',
+                    (true, false) if wall => '-',
+                    (false, true) if wall => '|',
+                    _ => ' ',
+                });
+            }
+            text.push('\n');
+        }
+        text
+    }
+
+    // expands the board into a grid of wall cells twice as fine, with the cells themselves at odd
+    // coordinates and the edges and corners between them at even ones
+    pub(crate) fn expand(&self) -> Vec<Vec<bool>> {
+        let mut grid = vec![vec![false; 2 * self.width() + 1]; 2 * self.height() + 1];
+        for (row, cells) in self.cells.iter().enumerate() {
+            for (col, walls) in cells.iter().enumerate() {
+                let Position { row: y, col: x } = expanded(Position::new(row, col));
+                grid[y - 1][x] |= walls.north;
+                grid[y][x + 1] |= walls.east;
+                grid[y + 1][x] |= walls.south;
+                grid[y][x - 1] |= walls.west;
+            }
+        }
+        // a corner is drawn whenever any edge meets it
+        for y in (0..grid.len()).step_by(2) {
+            for x in (0..grid[y].len()).step_by(2) {
+                grid[y][x] = (x > 0 && grid[y][x - 1])
+                    || (x + 1 < grid[y].len() && grid[y][x + 1])
+                    || (y > 0 && grid[y - 1][x])
+                    || (y + 1 < grid.len() && grid[y + 1][x]);
+            }
+        }
+        grid
+    }
+}
+
+// Thin-wall boards are drawn on a grid twice as fine as the cells. Cells sit at odd rows and
+// columns and hold ' ', 'T', 'M' or 'G', with as many 'M's as there are Minotaurs. The
+// characters between them are edges: '|' for a wall between two cells side by side, '-' for a
+// wall between two cells stacked vertically, and ' ' for no wall. An 'E' in place of a wall on
+// the outside of the board marks an exit. Corners, at even rows and columns, are '+' or ' ' and
+// only there for looks.
+// ```
+// +-+-+-+
+// |T  |G|
+// + +-+ +
+// |M    E
+// +-+-+-+
+// ```
+pub(crate) fn parse(board: &str) -> Result<Game, BoardError> {
+    let (edges, pieces) = parse_edges(board)?;
+    pieces.into_game(Layout::Edges(edges))
+}
+
+// where the cell at `pos` is on the grid twice as fine that the board is written and drawn on
+pub(crate) fn expanded(pos: Position) -> Position {
+    Position::new(pos.row * 2 + 1, pos.col * 2 + 1)
+}
+
+// parses a thin-wall board without checking which pieces are on it
+pub(crate) fn parse_edges(board: &str) -> Result<(EdgeGrid, Pieces), BoardError> {
+    let lines: Vec<Vec<char>> = board.lines().map(|line| line.chars().collect()).collect();
+    let text_width = lines.first().map_or(0, |line| line.len());
+    if lines.len() < 3
+        || lines.len().is_multiple_of(2)
+        || text_width < 3
+        || text_width.is_multiple_of(2)
+    {
+        return Err(BoardError::InvalidSize);
+    }
+    let (width, height) = ((text_width - 1) / 2, (lines.len() - 1) / 2);
+    let mut cells = vec![vec![Walls::default(); width]; height];
+    let mut exits = Vec::new();
+    let mut pieces = Pieces::default();
+    for (y, line) in lines.iter().enumerate() {
+        if line.len() > text_width {
+            return Err(BoardError::InvalidSize);
+        }
+        // editors may strip trailing spaces, so short lines are treated as padded with them
+        for x in 0..text_width {
+            let c = line.get(x).copied().unwrap_or(' ');
+            let (col, row) = (x / 2, y / 2);
+            let (at, cell) = (Position::new(y, x), Position::new(row, col));
+            match (x % 2 == 1, y % 2 == 1, c) {
+                // corners
+                (false, false, '+' | ' ') => {}
+                // walls between cells side by side
+                (false, true, '|') => {
+                    if col > 0 {
+                        cells[row][col - 1].east = true;
+                    }
+                    if col < width {
+                        cells[row][col].west = true;
+                    }
+                }
+                // walls between cells stacked vertically
+                (true, false, '-') => {
+                    if row > 0 {
+                        cells[row - 1][col].south = true;
+                    }
+                    if row < height {
+                        cells[row][col].north = true;
+                    }
+                }
+                (false, true, ' ') | (true, false, ' ') => {}
+                // exits, which can only be in the outer wall
+                (false, true, 'E') if x == 0 => exits.push((cell, Direction::Left)),
+                (false, true, 'E') if x + 1 == text_width => {
+                    exits.push((Position::new(row, col - 1), Direction::Right))
+                }
+                (true, false, 'E') if y == 0 => exits.push((cell, Direction::Up)),
+                (true, false, 'E') if y + 1 == lines.len() => {
+                    exits.push((Position::new(row - 1, col), Direction::Down))
+                }
+                (false, true, 'E') | (true, false, 'E') | (true, true, 'E') => {
+                    return Err(BoardError::ExitNotOnEdge(Location::of(at)));
+                }
+                // cells
+                (true, true, 'T') => {
+                    check_and_set(&mut pieces.theseus, cell, |first| {
+                        BoardError::MultipleTheseus {
+                            at: Location::of(at),
+                            first: Location::of(expanded(first)),
+                        }
+                    })?;
+                }
+                (true, true, 'M') => {
+                    pieces.minotaurs.push(cell);
+                }
+                (true, true, 'G') => {
+                    check_and_set(&mut pieces.goal, cell, |first| BoardError::MultipleGoal {
+                        at: Location::of(at),
+                        first: Location::of(expanded(first)),
+                    })?;
+                }
+                (true, true, ' ') => {}
+                (_, _, bad) => return Err(BoardError::InvalidCharacter(bad, Location::of(at))),
+            }
+        }
+    }
+    Ok((EdgeGrid { cells, exits }, pieces))
+}
@@ -0,0 +1,195 @@
+use crate::{Command, Direction, Game, GameStatus, Position};
+
+/// Something that happened during a turn, as returned by `Game::play_turn`
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum TurnEvent {
+    TheseusMoved {
+        from: Position,
+        to: Position,
+    },
+    /// Theseus tried to walk into a wall
+    TheseusBlocked {
+        at: Position,
+        direction: Direction,
+    },
+    /// The Minotaur that was at index `minotaur` of `Game::minotaur_positions` at the start of
+    /// the step took a step. If it landed on another Minotaur, the two merged and it's no longer
+    /// in the list.
+    MinotaurMoved {
+        minotaur: usize,
+        from: Position,
+        to: Position,
+    },
+    /// The Minotaur at index `minotaur` stayed where it was, which for most brains means it had
+    /// no step that brought it closer to Theseus
+    MinotaurStuck {
+        minotaur: usize,
+        at: Position,
+    },
+    /// Theseus and a Minotaur ended up on the same cell, which ends the game. Depending on the
+    /// `Ruleset`, this is either as soon as it happens or only at the end of the turn.
+    Caught {
+        at: Position,
+    },
+    /// Theseus stepped out through an exit
+    Escaped {
+        from: Position,
+        direction: Direction,
+    },
+}
+
+/// Everything that happened during a turn, in order, and how the game stands after it
+#[derive(Clone, Debug, PartialEq)]
+pub struct TurnOutcome {
+    pub events: Vec<TurnEvent>,
+    pub status: GameStatus,
+}
+
+impl Game {
+    /// Plays out one full turn by the game's `Ruleset`: Theseus follows `command` and every
+    /// Minotaur takes its steps, in the order the rules say, stopping as soon as the game is over.
+    /// Only moves and skips take a turn. Nothing happens for any other command, if the game is
+    /// already over, or if the rules don't allow `command`, though Theseus still walking into a
+    /// wall is noted.
+    pub fn play_turn(&mut self, command: Command) -> TurnOutcome {
+        self.play_turn_with(command, |_, _| {})
+    }
+
+    /// Like `play_turn`, but calls `before_step` with the game and the step number, counting
+    /// from 1, before the Minotaurs take each of their steps. Front ends use this to draw the
+    /// board partway through the turn.
+    pub fn play_turn_with(
+        &mut self,
+        command: Command,
+        mut before_step: impl FnMut(&Game, usize),
+    ) -> TurnOutcome {
+        let mut events = Vec::new();
+        if self.status() != GameStatus::Continue {
+            return self.outcome(events);
+        }
+        if !self.can_play(command) {
+            if let Ok(direction) = Direction::try_from(command) {
+                events.push(TurnEvent::TheseusBlocked {
+                    at: self.theseus,
+                    direction,
+                });
+            }
+            return self.outcome(events);
+        }
+        if self.rules.minotaurs_first {
+            self.minotaur_steps(&mut events, &mut before_step);
+            if self.turn_over(&mut events) {
+                return self.outcome(events);
+            }
+            self.move_theseus(command, &mut events);
+        } else {
+            self.move_theseus(command, &mut events);
+            if self.turn_over(&mut events) {
+                return self.outcome(events);
+            }
+            self.minotaur_steps(&mut events, &mut before_step);
+        }
+        // a catch that was only checked at the end of the turn
+        if self.status() == GameStatus::Lose && !self.rules.catch_mid_turn {
+            events.push(TurnEvent::Caught { at: self.theseus });
+        }
+        self.outcome(events)
+    }
+
+    /// Returns true if playing `command` would take a turn under the game's rules. Commands like
+    /// `Undo` and `Hint` never do, and the rules can rule out moves that leave Theseus where he
+    /// is, like skips and walking into walls.
+    pub fn can_play(&self, command: Command) -> bool {
+        match Direction::try_from(command) {
+            Ok(direction) => {
+                self.rules.allow_skip
+                    || self.step(self.theseus, direction).is_some()
+                    || self.layout.exits_through(self.theseus, direction)
+            }
+            Err(_) => command == Command::Skip && self.rules.allow_skip,
+        }
+    }
+
+    // takes every step the Minotaurs get this turn, stopping early if the game is over
+    fn minotaur_steps(
+        &mut self,
+        events: &mut Vec<TurnEvent>,
+        before_step: &mut impl FnMut(&Game, usize),
+    ) {
+        for step in 1..=self.rules.minotaur_steps {
+            before_step(self, step);
+            self.move_minotaurs(events);
+            if self.turn_over(events) {
+                return;
+            }
+        }
+    }
+
+    // returns true if the turn has to stop here because Theseus has won, or been caught and the
+    // rules say that counts partway through a turn, noting the catch in `events`
+    fn turn_over(&self, events: &mut Vec<TurnEvent>) -> bool {
+        match self.status() {
+            GameStatus::Win => true,
+            GameStatus::Lose if self.rules.catch_mid_turn => {
+                events.push(TurnEvent::Caught { at: self.theseus });
+                true
+            }
+            _ => false,
+        }
+    }
+
+    fn outcome(&self, events: Vec<TurnEvent>) -> TurnOutcome {
+        TurnOutcome {
+            events,
+            status: self.status(),
+        }
+    }
+
+    // moves Theseus one step or out through an exit, noting what happened in `events`
+    pub(crate) fn move_theseus(&mut self, command: Command, events: &mut Vec<TurnEvent>) {
+        let Ok(direction) = Direction::try_from(command) else {
+            return;
+        };
+        let from = self.theseus;
+        // moves into walls are ignored
+        if let Some(to) = self.step(from, direction) {
+            self.theseus = to;
+            events.push(TurnEvent::TheseusMoved { from, to });
+        } else if self.layout.exits_through(from, direction) {
+            self.escaped = true;
+            events.push(TurnEvent::Escaped { from, direction });
+        } else {
+            events.push(TurnEvent::TheseusBlocked {
+                at: from,
+                direction,
+            });
+        }
+    }
+
+    // moves every Minotaur one step, noting what happened in `events`
+    pub(crate) fn move_minotaurs(&mut self, events: &mut Vec<TurnEvent>) {
+        let mut i = 0;
+        // counts every Minotaur, including the ones that merge away, so events can name them
+        let mut minotaur = 0;
+        while i < self.minotaurs.len() {
+            let from = self.minotaurs[i];
+            let to = self
+                .brain
+                .choose(self, from)
+                .and_then(|direction| self.minotaur_step(from, direction))
+                .unwrap_or(from);
+            if to == from {
+                events.push(TurnEvent::MinotaurStuck { minotaur, at: from });
+            } else {
+                events.push(TurnEvent::MinotaurMoved { minotaur, from, to });
+            }
+            if self.minotaurs.contains(&to) && from != to {
+                self.minotaurs.remove(i);
+            } else {
+                self.minotaurs[i] = to;
+                i += 1;
+            }
+            minotaur += 1;
+        }
+    }
+}
@@ -1,3 +1,11 @@
+// the original tests are kept as they were written, which newer lints would word differently
+#![allow(
+    unused_variables,
+    clippy::expect_fun_call,
+    clippy::needless_borrow,
+    clippy::useless_vec
+)]
+
 fn check_board_matches_game(board: &str, game: &theseus::Game) {
     for (i, row) in board.lines().enumerate() {
         for (j, cell) in row.chars().enumerate() {
@@ -291,3 +299,1271 @@ fn test_status_continue_3() {
     game.minotaur_move();
     assert_eq!(game.status(), theseus::GameStatus::Continue);
 }
+
+fn play_commands(game: &mut theseus::Game, commands: &[theseus::Command]) {
+    for &command in commands {
+        game.theseus_move(command);
+        for _ in 0..2 {
+            if game.status() == theseus::GameStatus::Continue {
+                game.minotaur_move();
+            }
+        }
+    }
+}
+
+#[test]
+fn test_solve_shortest() {
+    let board = "XXXXXXX\n\
+                 XT   GX\n\
+                 XXXXXXX\n\
+                 XM    X\n\
+                 XXXXXXX\n";
+    let game = theseus::Game::from_board(board).unwrap();
+    assert_eq!(game.solve(), Ok(vec![theseus::Command::Right; 4]));
+}
+
+#[test]
+fn test_solve_board_files() {
+    for board in [
+        include_str!("../board0.txt"),
+        include_str!("../board1.txt"),
+        include_str!("../board2.txt"),
+    ] {
+        let mut game = theseus::Game::from_board(board).unwrap();
+        let commands = game.solve().expect("Expected board to be solvable");
+        play_commands(&mut game, &commands);
+        assert_eq!(game.status(), theseus::GameStatus::Win);
+    }
+}
+
+#[test]
+fn test_solve_unsolvable() {
+    let board = "XXXXX\n\
+                 XTXGX\n\
+                 XXXXX\n\
+                 XM  X\n\
+                 XXXXX\n";
+    let game = theseus::Game::from_board(board).unwrap();
+    assert_eq!(game.solve(), Err(theseus::Unsolvable));
+}
+
+#[test]
+fn test_history_undo_redo() {
+    let board = "XXXXXX\n\
+                 X    X\n\
+                 XM T X\n\
+                 XG   X\n\
+                 XXXXXX\n";
+    let moved = "XXXXXX\n\
+                 X    X\n\
+                 XM  TX\n\
+                 XG   X\n\
+                 XXXXXX\n";
+    let mut game = theseus::Game::from_board(board).unwrap();
+    let mut history = theseus::History::new();
+    assert!(!history.undo(&mut game));
+
+    history.record(&game, theseus::Command::Right);
+    game.theseus_move(theseus::Command::Right);
+    check_board_matches_game(moved, &game);
+    assert_eq!(history.commands(), vec![theseus::Command::Right]);
+
+    assert!(history.undo(&mut game));
+    check_board_matches_game(board, &game);
+    assert!(history.commands().is_empty());
+
+    assert!(history.redo(&mut game));
+    check_board_matches_game(moved, &game);
+    assert!(!history.redo(&mut game));
+}
+
+#[test]
+fn test_hint() {
+    let board = "XXXXXXX\n\
+                 XT   GX\n\
+                 XXXXXXX\n\
+                 XM    X\n\
+                 XXXXXXX\n";
+    let game = theseus::Game::from_board(board).unwrap();
+    assert_eq!(game.hint(), Some(theseus::Command::Right));
+
+    let board = "XXXXX\n\
+                 XTXGX\n\
+                 XXXXX\n\
+                 XM  X\n\
+                 XXXXX\n";
+    let game = theseus::Game::from_board(board).unwrap();
+    assert_eq!(game.hint(), None);
+}
+
+#[test]
+fn test_replay_round_trip() {
+    let board = "XXXXXXX\n\
+                 XT   GX\n\
+                 XXXXXXX\n\
+                 XM    X\n\
+                 XXXXXXX\n";
+    let replay = theseus::Replay::new(
+        board,
+        vec![theseus::Command::Right; 4],
+        theseus::GameStatus::Win,
+    );
+    let parsed = theseus::Replay::parse(&replay.to_string()).unwrap();
+    assert_eq!(parsed, replay);
+    assert_eq!(parsed.play().unwrap().status(), theseus::GameStatus::Win);
+}
+
+#[test]
+fn test_replay_brain_round_trip() {
+    use theseus::{Brain, Command, GameStatus, Replay, ReplayError, Ruleset};
+    // only the hunter finds its way around the wall in time
+    let board = "XXXXXXX\n\
+                 XM X TX\n\
+                 X  X  X\n\
+                 X    GX\n\
+                 XXXXXXX\n";
+    let mut replay = Replay::new(board, vec![Command::Skip; 4], GameStatus::Lose);
+    replay.brain = Brain::Hunter;
+    replay.rules = Ruleset {
+        catch_mid_turn: false,
+        ..Ruleset::default()
+    };
+    let text = replay.to_string();
+    assert!(text.contains("brain: hunter\ncatch-at-end\n"));
+    let parsed = Replay::parse(&text).unwrap();
+    assert_eq!(parsed, replay);
+    let game = parsed.start().unwrap();
+    assert_eq!(game.brain().name(), "hunter");
+    assert_eq!(game.rules(), replay.rules);
+    assert_eq!(parsed.play().unwrap().status(), GameStatus::Lose);
+    // the same moves against the classic brain don't lose
+    let classic = Replay::new(board, vec![Command::Skip; 4], GameStatus::Lose);
+    assert_eq!(classic.play().unwrap().status(), GameStatus::Continue);
+
+    assert_eq!(
+        Replay::parse("status: lose\nbrain: sleepy\nboard:\n"),
+        Err(ReplayError::UnknownBrain(String::from("sleepy")))
+    );
+}
+
+#[test]
+fn test_replay_parse_errors() {
+    assert_eq!(
+        theseus::Replay::parse("status: win\nmoves: right\n"),
+        Err(theseus::ReplayError::MissingBoard)
+    );
+    assert_eq!(
+        theseus::Replay::parse("status: win\nmoves: right jump\nboard:\n"),
+        Err(theseus::ReplayError::InvalidMove(String::from("jump")))
+    );
+    assert_eq!(
+        theseus::Replay::parse("status: draw\nboard:\n"),
+        Err(theseus::ReplayError::InvalidStatus(String::from("draw")))
+    );
+}
+
+#[test]
+fn test_parse_moves() {
+    use theseus::Command::*;
+    assert_eq!(
+        theseus::parse_moves("wwd sd.\n"),
+        Ok(vec![Up, Up, Right, Down, Right, Skip])
+    );
+    assert_eq!(theseus::parse_moves("wx"), Err(theseus::InvalidMove('x')));
+    assert_eq!(theseus::format_moves(&[Left, Undo, Down, Skip]), "as.");
+
+    let mut game = theseus::Game::from_board(include_str!("../board0.txt")).unwrap();
+    let moves = theseus::parse_moves("aaddddssdd").unwrap();
+    assert_eq!(game.play_moves(&moves), theseus::GameStatus::Win);
+}
+
+#[test]
+fn test_generate_solvable() {
+    let mut options = theseus::GeneratorOptions::new(15, 11);
+    options.seed = 7;
+    options.min_moves = 12;
+    let board = theseus::generate(&options).unwrap();
+    assert_eq!(theseus::generate(&options).unwrap(), board);
+    assert_eq!(board.lines().count(), 11);
+    assert!(board.lines().all(|line| line.len() == 15));
+
+    let game = theseus::Game::from_board(&board).unwrap();
+    assert!(game.solve().unwrap().len() >= 12);
+
+    // boards of even size use every column and row inside the outer walls too
+    let board = theseus::generate(&theseus::GeneratorOptions::new(6, 8)).unwrap();
+    let lines: Vec<&str> = board.lines().collect();
+    assert_eq!(lines.len(), 8);
+    assert!(lines.iter().all(|line| line.len() == 6));
+    for col in 1..5 {
+        assert!(lines[1..7].iter().any(|line| &line[col..=col] != "X"));
+    }
+    for line in &lines[1..7] {
+        assert_ne!(&line[1..5], "XXXX");
+    }
+    assert!(theseus::Game::from_board(&board).unwrap().solve().is_ok());
+
+    assert_eq!(
+        theseus::generate(&theseus::GeneratorOptions::new(4, 9)),
+        Err(theseus::GenerateError::TooSmall)
+    );
+}
+
+#[test]
+fn test_thin_board() {
+    let board = "+-+-+-+\n\
+                 |T  |G|\n\
+                 + +-+ +\n\
+                 |M    |\n\
+                 +-+-+-+\n";
+    let mut game = theseus::Game::from_thin_board(board).unwrap();
+    assert!(game.is_theseus(0, 0));
+    assert!(game.is_minotaur(1, 0));
+    assert!(game.is_goal(0, 2));
+    assert!(game.is_empty(1, 1));
+
+    // the wall between (0, 1) and (0, 2) stops Theseus
+    game.theseus_move(theseus::Command::Right);
+    game.theseus_move(theseus::Command::Right);
+    assert!(game.is_theseus(0, 1));
+    // and the wall under (0, 1) stops the Minotaur from chasing him vertically
+    game.minotaur_move();
+    assert!(game.is_minotaur(1, 1));
+    game.minotaur_move();
+    assert!(game.is_minotaur(1, 1));
+
+    assert!(theseus::Game::parse(board).is_ok());
+    assert!(theseus::Game::parse(include_str!("../board3.txt"))
+        .unwrap()
+        .solve()
+        .is_ok());
+}
+
+#[test]
+fn test_thin_board_errors() {
+    assert_eq!(
+        theseus::Game::from_thin_board("+-+-+\n|T-M|\n+-+-+\n").err(),
+        Some(theseus::BoardError::InvalidCharacter(
+            '-',
+            theseus::Location { line: 2, column: 3 }
+        ))
+    );
+    assert_eq!(
+        theseus::Game::from_thin_board("+-+-+\n|T|M|\n+-+-+\n").err(),
+        Some(theseus::BoardError::NoGoal)
+    );
+    assert_eq!(
+        theseus::Game::from_thin_board("+-+-+\n|T|M|\n").err(),
+        Some(theseus::BoardError::InvalidSize)
+    );
+}
+
+#[test]
+fn test_key_command() {
+    use theseus::Command::*;
+    assert_eq!(theseus::key_command(b"\x1b[A"), Some(Up));
+    assert_eq!(theseus::key_command(b"\x1b[B"), Some(Down));
+    assert_eq!(theseus::key_command(b"\x1b[C"), Some(Right));
+    assert_eq!(theseus::key_command(b"\x1b[D"), Some(Left));
+    assert_eq!(theseus::key_command(b"a"), Some(Left));
+    assert_eq!(theseus::key_command(b" "), Some(Skip));
+    assert_eq!(theseus::key_command(b"u"), Some(Undo));
+    assert_eq!(theseus::key_command(b"x"), None);
+}
+
+#[test]
+fn test_theme_by_name() {
+    assert_eq!(
+        theseus::Theme::by_name("colorblind"),
+        Some(theseus::Theme::colorblind())
+    );
+    assert_eq!(theseus::Theme::by_name("plain").unwrap().wall, None);
+    assert_eq!(theseus::Theme::by_name("neon"), None);
+}
+
+#[test]
+fn test_multiple_minotaurs() {
+    let start_board = "XXXXXXX\n\
+                       XMM T X\n\
+                       XG    X\n\
+                       XXXXXXX\n";
+    // the first Minotaur runs into the second and they merge before the survivor moves on
+    let finish_board = "XXXXXXX\n\
+                        X  MT X\n\
+                        XG    X\n\
+                        XXXXXXX\n";
+    let mut game = theseus::Game::from_board(start_board).unwrap();
+    check_board_matches_game(start_board, &game);
+    game.minotaur_move();
+    check_board_matches_game(finish_board, &game);
+
+    let board = "XXXXXXX\n\
+                 XM T MX\n\
+                 XXXXX X\n\
+                 XG    X\n\
+                 XXXXXXX\n";
+    let mut game = theseus::Game::from_board(board).unwrap();
+    check_board_matches_game(board, &game);
+    game.theseus_move(theseus::Command::Right);
+    game.minotaur_move();
+    assert_eq!(game.status(), theseus::GameStatus::Lose);
+}
+
+#[test]
+fn test_parse_pack() {
+    let levels = theseus::parse_pack(include_str!("../levels.pack")).unwrap();
+    assert_eq!(levels.len(), 4);
+    assert_eq!(levels[0].title, "The Corridor");
+    for level in levels {
+        let mut game = level.game.clone();
+        let solution = level.solution.unwrap();
+        assert_eq!(Some(solution.len()), level.par);
+        assert_eq!(game.play_moves(&solution), theseus::GameStatus::Win);
+    }
+}
+
+#[test]
+fn test_parse_pack_errors() {
+    let pack = "@level Fine\n\
+                XXXX\n\
+                XMTX\n\
+                X GX\n\
+                XXXX\n\
+                \n\
+                @level Broken\n\
+                @par 3\n\
+                XXXX\n\
+                XMTX\n\
+                XXXX\n";
+    let err = theseus::parse_pack(pack).err().unwrap();
+    assert_eq!(err.level, 2);
+    assert_eq!(err.title, "Broken");
+    assert_eq!(
+        err.kind,
+        theseus::PackErrorKind::Board(theseus::BoardError::NoGoal)
+    );
+
+    let err = theseus::parse_pack("@level Odd\n@par many\n")
+        .err()
+        .unwrap();
+    assert_eq!(
+        err.kind,
+        theseus::PackErrorKind::InvalidPar(String::from("many"))
+    );
+    assert_eq!(
+        theseus::parse_pack("XXXX\n").err().unwrap().kind,
+        theseus::PackErrorKind::TextBeforeLevel
+    );
+
+    // blank lines between the headers and the board aren't part of it
+    let levels = theseus::parse_pack("@level T\n\n+-+-+-+\n|T M G|\n+-+-+-+\n").unwrap();
+    assert_eq!(levels[0].board, "+-+-+-+\n|T M G|\n+-+-+-+\n");
+    let pack = "@level Spaced\n\n@par 3\n\nXXXXX\nXTM?X\nXXXXX\n";
+    assert_eq!(
+        theseus::parse_pack(pack).err().unwrap().kind,
+        theseus::PackErrorKind::Board(theseus::BoardError::InvalidCharacter(
+            '?',
+            theseus::Location { line: 6, column: 4 }
+        ))
+    );
+    assert_eq!(
+        theseus::check_pack("@level Spaced\n\nXXXXX\nXTMGX\nXXXXX\n").unwrap()[0].1,
+        vec![theseus::Problem::Unsolvable]
+    );
+}
+
+#[test]
+fn test_campaign_progress() {
+    let levels = theseus::parse_pack(include_str!("../levels.pack")).unwrap();
+    let mut progress = theseus::Progress::new();
+    assert_eq!(progress.next_level(&levels), Some(0));
+    assert!(progress.is_unlocked(&levels, 0));
+    assert!(!progress.is_unlocked(&levels, 1));
+
+    assert!(progress.record_win("The Corridor", 12));
+    assert!(progress.record_win("The Corridor", 10));
+    assert!(!progress.record_win("The Corridor", 11));
+    assert_eq!(progress.best("The Corridor"), Some(10));
+    assert_eq!(progress.next_level(&levels), Some(1));
+    assert!(progress.is_unlocked(&levels, 1));
+
+    let saved = progress.to_string();
+    assert_eq!(saved, "10 The Corridor\n");
+    assert_eq!(theseus::Progress::parse(&saved), Ok(progress));
+    assert_eq!(
+        theseus::Progress::parse("10 The Corridor\nten Side Door\n"),
+        Err(theseus::InvalidProgress { line: 2 })
+    );
+}
+
+#[test]
+fn test_save_and_load() {
+    let board = "XXXXXXX\n\
+                 XM G TX\n\
+                 XXXXXXX\n";
+    let mut game = theseus::Game::from_board(board).unwrap();
+    play_commands(&mut game, &[theseus::Command::Skip]);
+    // the Minotaur is now standing on the goal, which a board file can't show
+    assert!(game.is_minotaur(1, 3));
+    let saved = game.save_state();
+    let loaded = theseus::Game::load_state(&saved).unwrap();
+    assert!(loaded.is_minotaur(1, 3));
+    assert!(loaded.is_goal(1, 3));
+    assert!(loaded.is_theseus(1, 5));
+    assert!(loaded.is_wall(0, 0));
+    assert_eq!(loaded.save_state(), saved);
+
+    let mut game = theseus::Game::parse(include_str!("../board3.txt")).unwrap();
+    play_commands(&mut game, &[theseus::Command::Left, theseus::Command::Left]);
+    let saved = game.save_state();
+    let mut loaded = theseus::Game::load_state(&saved).unwrap();
+    assert_eq!(loaded.save_state(), saved);
+    // the restored game plays on exactly like the original
+    play_commands(&mut game, &[theseus::Command::Up]);
+    play_commands(&mut loaded, &[theseus::Command::Up]);
+    assert_eq!(loaded.save_state(), game.save_state());
+}
+
+#[test]
+fn test_load_errors() {
+    use theseus::{BoardError, Game, SaveError};
+    assert_eq!(
+        Game::load_state("theseus 1 1\n").err(),
+        Some(SaveError::MissingBoard)
+    );
+    assert_eq!(
+        Game::load_state("theseus 1\nboard:\nXXX\n").err(),
+        Some(SaveError::InvalidLine(String::from("theseus 1")))
+    );
+    assert_eq!(
+        Game::load_state("dragon 1 1\nboard:\nXXX\n").err(),
+        Some(SaveError::InvalidLine(String::from("dragon 1 1")))
+    );
+    assert_eq!(
+        Game::load_state("theseus 1 1\nminotaur 1 2\nboard:\nXXXXX\nX   X\nXXXXX\n").err(),
+        Some(SaveError::Board(BoardError::NoGoal))
+    );
+    assert_eq!(
+        Game::load_state("theseus 1 1\nminotaur 1 2\ngoal 0 3\nboard:\nXXXXX\nX   X\nXXXXX\n")
+            .err(),
+        Some(SaveError::InvalidPosition(theseus::Position::new(0, 3)))
+    );
+}
+
+#[test]
+fn test_board_error_locations() {
+    use theseus::{BoardError, Game, Location};
+    let board = "XXXXXXX\n\
+                 X T  GX\n\
+                 X  M  X\n\
+                 X   T X\n\
+                 XXXXXXX\n";
+    let err = Game::from_board(board).unwrap_err();
+    assert_eq!(
+        err,
+        BoardError::MultipleTheseus {
+            at: Location { line: 4, column: 5 },
+            first: Location { line: 2, column: 3 },
+        }
+    );
+    assert_eq!(err.location(), Some(Location { line: 4, column: 5 }));
+    assert_eq!(err.earlier(), Some(Location { line: 2, column: 3 }));
+    assert_eq!(
+        err.render("board.txt", board),
+        "error: more than one Theseus\n\
+         \x20--> board.txt:4:5\n\
+         \x20 |\n\
+         2 | X T  GX\n\
+         \x20 |   - first Theseus here\n\
+         4 | X   T X\n\
+         \x20 |     ^ another Theseus\n"
+    );
+
+    let err = Game::from_board("XXXXX\nXTMGX\nXX?XX\n").unwrap_err();
+    assert_eq!(
+        err,
+        BoardError::InvalidCharacter('?', Location { line: 3, column: 3 })
+    );
+    assert_eq!(err.earlier(), None);
+
+    let err = Game::from_thin_board("+-+-+-+\n|T G G|\n+-+-+-+\n")
+        .err()
+        .unwrap();
+    assert_eq!(
+        err,
+        BoardError::MultipleGoal {
+            at: Location { line: 2, column: 6 },
+            first: Location { line: 2, column: 4 },
+        }
+    );
+    // both goals are on the same line, so it's only quoted once
+    assert_eq!(
+        err.render("thin.txt", "+-+-+-+\n|T G G|\n+-+-+-+\n"),
+        "error: more than one goal\n\
+         \x20--> thin.txt:2:6\n\
+         \x20 |\n\
+         2 | |T G G|\n\
+         \x20 |    - first goal here\n\
+         \x20 |      ^ another goal\n"
+    );
+    assert_eq!(
+        BoardError::NoGoal.render("board.txt", ""),
+        "error: no goal or exit on the board\n --> board.txt\n"
+    );
+    // an exit is a valid character, just not away from the edge
+    let board = "XXXXXXX\nXM E TX\nXXXXXXG\n";
+    assert_eq!(
+        Game::from_board(board)
+            .unwrap_err()
+            .render("board.txt", board),
+        "error: exit must be in the outer wall\n\
+         \x20--> board.txt:2:4\n\
+         \x20 |\n\
+         2 | XM E TX\n\
+         \x20 |    ^ inside the board\n"
+    );
+
+    // boards in a pack are located by their line in the whole pack
+    let pack = "@level One\n@par 3\nXXXXX\nXTMGX\nXX?XX\n";
+    assert_eq!(
+        theseus::parse_pack(pack).err().map(|err| err.kind),
+        Some(theseus::PackErrorKind::Board(BoardError::InvalidCharacter(
+            '?',
+            Location { line: 5, column: 3 }
+        )))
+    );
+}
+
+#[test]
+fn test_check_board() {
+    use theseus::{check_board, check_pack, BoardError, Location, Problem};
+    assert_eq!(check_board(include_str!("../board1.txt")), vec![]);
+    assert_eq!(check_board(include_str!("../board2.txt")), vec![]);
+    assert_eq!(check_board(include_str!("../board3.txt")), vec![]);
+
+    // every problem is reported, not just the first
+    let board = "XXXXXXX\n\
+                 XQT  GX\n\
+                 X  M  X\n\
+                 X T?  X\n\
+                 XXXXXXX\n";
+    assert_eq!(
+        check_board(board),
+        vec![
+            Problem::Board(BoardError::InvalidCharacter(
+                'Q',
+                Location { line: 2, column: 2 }
+            )),
+            Problem::Board(BoardError::MultipleTheseus {
+                at: Location { line: 4, column: 3 },
+                first: Location { line: 2, column: 3 },
+            }),
+            Problem::Board(BoardError::InvalidCharacter(
+                '?',
+                Location { line: 4, column: 4 }
+            )),
+            // the rest of the checks carry on as if the bad characters weren't there
+            Problem::Unsolvable,
+        ]
+    );
+
+    let board = "XXXXXXX\n\
+                 X   TG\n\
+                 XM XXXX\n\
+                 XXXXXXX\n";
+    assert_eq!(
+        check_board(board),
+        vec![Problem::RaggedRow {
+            line: 2,
+            length: 6,
+            expected: 7
+        }]
+    );
+
+    let board = "XXXXXX\n\
+                 XM  T \n\
+                 XXXXXG\n";
+    assert_eq!(
+        check_board(board),
+        vec![Problem::OpenBoundary(Location { line: 2, column: 6 })]
+    );
+
+    let board = "XXXXXXX\n\
+                 XT X GX\n\
+                 XM X  X\n\
+                 XXXXXXX\n";
+    assert_eq!(
+        check_board(board),
+        vec![Problem::GoalUnreachable {
+            goal: Location { line: 2, column: 6 },
+            theseus: Location { line: 2, column: 2 },
+        }]
+    );
+
+    let board = "+-+-+-+\n\
+                 |T   M \n\
+                 +-+-+-+\n\
+                 |G    |\n\
+                 +-+-+-+\n";
+    assert_eq!(
+        check_board(board),
+        vec![
+            Problem::OpenBoundary(Location { line: 2, column: 7 }),
+            Problem::GoalUnreachable {
+                goal: Location { line: 4, column: 2 },
+                theseus: Location { line: 2, column: 2 },
+            },
+        ]
+    );
+
+    let board = "XXXXX\n\
+                 XTMGX\n\
+                 XXXXX\n";
+    assert_eq!(check_board(board), vec![Problem::Unsolvable]);
+
+    // packs are checked a level at a time, with locations in the whole pack
+    let levels = check_pack(include_str!("../levels.pack")).unwrap();
+    assert!(levels.iter().all(|(_, problems)| problems.is_empty()));
+    let pack = "@level Fine\nXXXXX\nXTMGX\nXXXXX\n\n@level Broken\n@par 3\nXXXXX\nXTMQ\nXXXXX\n";
+    assert_eq!(
+        check_pack(pack).unwrap(),
+        vec![
+            (String::from("Fine"), vec![Problem::Unsolvable]),
+            (
+                String::from("Broken"),
+                vec![
+                    Problem::Board(BoardError::InvalidCharacter(
+                        'Q',
+                        Location { line: 9, column: 4 }
+                    )),
+                    Problem::RaggedRow {
+                        line: 9,
+                        length: 4,
+                        expected: 5
+                    },
+                    Problem::Board(BoardError::NoGoal),
+                ]
+            ),
+        ]
+    );
+    assert_eq!(
+        check_pack("@level Broken\n@par many\nXXXXX\n")
+            .unwrap_err()
+            .kind,
+        theseus::PackErrorKind::InvalidPar(String::from("many"))
+    );
+}
+
+#[test]
+fn test_ragged_board() {
+    use theseus::Command::*;
+    let board = "XXXXXX\n\
+                 XM   T\n\
+                 XXX G\n";
+    for game in [
+        theseus::Game::from_board(board).unwrap(),
+        theseus::Game::from_board_padded(board).unwrap(),
+    ] {
+        // everything off the board is a wall, including past the end of the short last row
+        assert!(game.is_wall(1, 6));
+        assert!(game.is_wall(2, 5));
+        assert!(game.is_wall(3, 0));
+        assert!(!game.is_wall(2, 3));
+        assert!(!game.is_empty(2, 5));
+
+        let mut game = game.clone();
+        game.theseus_move(Right);
+        game.theseus_move(Down);
+        assert!(game.is_theseus(1, 5));
+        game.theseus_move(Left);
+        game.theseus_move(Down);
+        assert!(game.is_theseus(2, 4));
+        game.theseus_move(Down);
+        game.theseus_move(Right);
+        assert!(game.is_theseus(2, 4));
+        assert_eq!(game.status(), theseus::GameStatus::Win);
+    }
+
+    // wandering around a board with an open edge never leaves it
+    let mut game = theseus::Game::from_board(include_str!("../board2.txt")).unwrap();
+    game.play_moves(&[
+        Right, Right, Right, Right, Right, Down, Down, Left, Up, Up, Up, Up,
+    ]);
+}
+
+#[test]
+fn test_exits() {
+    use theseus::Command::*;
+    use theseus::{check_board, BoardError, Game, GameStatus, Location, Problem};
+    let board = "XXXXXXX\n\
+                 X M T E\n\
+                 XXXXXXX\n";
+    let game = Game::from_board(board).unwrap();
+    assert!(game.is_exit(1, 6));
+    assert!(!game.is_wall(1, 6));
+    assert!(!game.is_empty(1, 6));
+    assert_eq!(game.solve(), Ok(vec![Right, Right]));
+    assert_eq!(check_board(board), vec![]);
+
+    let mut escaped = game.clone();
+    assert_eq!(escaped.play_moves(&[Right, Right]), GameStatus::Win);
+    assert!(escaped.has_escaped());
+    // Theseus is left where he stepped out from
+    assert!(escaped.is_theseus(1, 5));
+    let saved = escaped.save_state();
+    assert!(Game::load_state(&saved).unwrap().has_escaped());
+
+    // the Minotaur can't follow Theseus out, even when it's right next to the exit
+    let board = "XXXXXX\n\
+                 XT  ME\n\
+                 XXXXXX\n";
+    let mut game = Game::from_board(board).unwrap();
+    game.minotaur_move();
+    game.minotaur_move();
+    assert!(game.is_minotaur(1, 2));
+    assert!(!game.has_escaped());
+
+    // thin-wall boards mark exits in the outer wall
+    let board = "+-+-+-+\n\
+                 |M   TE\n\
+                 +-+-+-+\n";
+    let mut game = Game::from_thin_board(board).unwrap();
+    assert_eq!(check_board(board), vec![]);
+    let saved = game.save_state();
+    assert!(saved.ends_with("board:\n+-+-+-+\n|     E\n+-+-+-+\n"));
+    assert_eq!(Game::load_state(&saved).unwrap().save_state(), saved);
+    game.theseus_move(Right);
+    assert_eq!(game.status(), GameStatus::Win);
+
+    assert_eq!(
+        Game::from_thin_board("+-+-+-+\n|M E T|\n+-+-+-+\n").err(),
+        Some(BoardError::ExitNotOnEdge(Location { line: 2, column: 4 }))
+    );
+    // and whole-cell boards in the cells around the edge
+    assert_eq!(
+        Game::from_board("XXXXXXX\nXM E TX\nXXXXXXX\n").err(),
+        Some(BoardError::ExitNotOnEdge(Location { line: 2, column: 4 }))
+    );
+    assert!(Game::from_board("XXXXXXX\nXM  TXE\nXXXXXX\n").is_ok());
+    assert_eq!(
+        Game::from_board("XXXXX\nXM TX\nXXXXX\n").err(),
+        Some(BoardError::NoGoal)
+    );
+    let board = "XXXXXXX\n\
+                 XT X ME\n\
+                 XXXXXXX\n";
+    assert_eq!(
+        check_board(board),
+        vec![
+            Problem::MinotaurUnreachable(Location { line: 2, column: 6 }),
+            Problem::ExitUnreachable(Location { line: 2, column: 2 }),
+        ]
+    );
+}
+
+#[test]
+fn test_position_and_direction() {
+    use theseus::{Command, Direction, Game, Position};
+    let pos = Position::new(0, 2);
+    assert_eq!(pos.step(Direction::Down), Some(Position { row: 1, col: 2 }));
+    assert_eq!(pos.step(Direction::Up), None);
+    assert_eq!(
+        pos.neighbors().collect::<Vec<_>>(),
+        vec![
+            Position::new(1, 2),
+            Position::new(0, 1),
+            Position::new(0, 3)
+        ]
+    );
+    assert!(pos.is_within(3, 1));
+    assert!(!pos.is_within(2, 1));
+    assert_eq!(pos.step_within(Direction::Right, 3, 1), None);
+    assert_eq!(
+        pos.step_within(Direction::Left, 3, 1),
+        Some(Position::new(0, 1))
+    );
+    assert_eq!(
+        Position::new(0, 0)
+            .neighbors_within(2, 2)
+            .collect::<Vec<_>>(),
+        vec![Position::new(1, 0), Position::new(0, 1)]
+    );
+    for direction in Direction::ALL {
+        assert_eq!(Direction::try_from(Command::from(direction)), Ok(direction));
+        let there = Position::new(5, 5).step(direction).unwrap();
+        assert_eq!(there.step(direction.opposite()), Some(Position::new(5, 5)));
+    }
+    assert_eq!(Direction::try_from(Command::Skip), Err(Command::Skip));
+
+    // positions from the accessors agree with the is_* functions
+    let game = Game::from_board(include_str!("../board0.txt")).unwrap();
+    let theseus = game.theseus_pos();
+    assert!(game.is_theseus(theseus.row, theseus.col));
+    let minotaur = game.minotaur_pos();
+    assert!(game.is_minotaur(minotaur.row, minotaur.col));
+    assert_eq!(game.minotaur_positions(), &[minotaur]);
+    let goal = game.goal_pos().unwrap();
+    assert!(game.is_goal(goal.row, goal.col));
+}
+
+#[test]
+fn test_grid() {
+    use theseus::{Cell, Game, Grid, Position};
+    let game = Game::from_board("XXXX\nXT E\nXMGX\nXXX\n").unwrap();
+    let grid = game.grid().unwrap();
+    assert_eq!((grid.width(), grid.height()), (4, 4));
+    assert!(!grid.is_rectangular());
+    assert_eq!(grid.get(Position::new(0, 0)), Some(Cell::Wall));
+    assert_eq!(grid.get(Position::new(1, 1)), Some(Cell::Open));
+    assert_eq!(grid.get(Position::new(1, 3)), Some(Cell::Exit));
+    // off the end of a short row and off the board entirely
+    assert_eq!(grid.get(Position::new(3, 3)), None);
+    assert_eq!(grid.get(Position::new(9, 0)), None);
+    assert_eq!(grid.cells().count(), 15);
+    assert_eq!(grid.walls().count(), 10);
+    assert!(grid.walls().all(|pos| game.is_wall(pos.row, pos.col)));
+    assert_eq!(
+        grid.cells()
+            .filter(|&(_, cell)| cell == Cell::Exit)
+            .map(|(pos, _)| pos)
+            .collect::<Vec<_>>(),
+        vec![Position::new(1, 3)]
+    );
+
+    // building a grid from walls or cells
+    let walls = vec![vec![true, true], vec![true, false]];
+    let from_walls = Grid::from_walls(walls.clone());
+    assert_eq!(from_walls, Grid::from(walls.clone()));
+    assert_eq!(from_walls.to_walls(), walls);
+    assert_eq!(
+        from_walls.walls().collect::<Vec<_>>(),
+        vec![
+            Position::new(0, 0),
+            Position::new(0, 1),
+            Position::new(1, 0)
+        ]
+    );
+    let from_cells = Grid::from_cells(vec![vec![Cell::Wall, Cell::Exit], vec![Cell::Open]]);
+    assert_eq!(from_cells.get(Position::new(0, 1)), Some(Cell::Exit));
+    assert_eq!(from_cells.to_walls(), vec![vec![true, false], vec![false]]);
+
+    // thin-wall boards have no grid of wall cells
+    let thin = Game::parse("+-+-+\n|T M|\n+ +-+\n|G  |\n+-+-+\n").unwrap();
+    assert!(thin.grid().is_none());
+}
+
+#[test]
+fn test_play_turn() {
+    use theseus::{Command, Direction, Game, GameStatus, Position, TurnEvent};
+    let board = "XXXXXXX\n\
+                 XM   TX\n\
+                 X X  XX\n\
+                 XG    E\n\
+                 XXXXXXX\n";
+    let mut game = Game::from_board(board).unwrap();
+    let mut steps = Vec::new();
+    let outcome = game.play_turn_with(Command::Up, |game, step| {
+        steps.push((step, game.minotaur_pos()));
+    });
+    assert_eq!(
+        steps,
+        vec![(1, Position::new(1, 1)), (2, Position::new(1, 2))]
+    );
+    assert_eq!(
+        outcome.events,
+        vec![
+            TurnEvent::TheseusBlocked {
+                at: Position::new(1, 5),
+                direction: Direction::Up
+            },
+            TurnEvent::MinotaurMoved {
+                minotaur: 0,
+                from: Position::new(1, 1),
+                to: Position::new(1, 2)
+            },
+            TurnEvent::MinotaurMoved {
+                minotaur: 0,
+                from: Position::new(1, 2),
+                to: Position::new(1, 3)
+            },
+        ]
+    );
+    assert_eq!(outcome.status, GameStatus::Continue);
+
+    // the turn stops as soon as Theseus is caught
+    let mut caught = game.clone();
+    let outcome = caught.play_turn(Command::Left);
+    assert_eq!(
+        outcome.events,
+        vec![
+            TurnEvent::TheseusMoved {
+                from: Position::new(1, 5),
+                to: Position::new(1, 4)
+            },
+            TurnEvent::MinotaurMoved {
+                minotaur: 0,
+                from: Position::new(1, 3),
+                to: Position::new(1, 4)
+            },
+            TurnEvent::Caught {
+                at: Position::new(1, 4)
+            },
+        ]
+    );
+    assert_eq!(outcome.status, GameStatus::Lose);
+    // and nothing happens once the game is over
+    assert!(caught.play_turn(Command::Skip).events.is_empty());
+
+    // a Minotaur with no step closer to Theseus is stuck
+    let mut game = Game::from_board("XXXXX\nXMXTE\nXXXXX\n").unwrap();
+    let stuck = TurnEvent::MinotaurStuck {
+        minotaur: 0,
+        at: Position::new(1, 1),
+    };
+    assert_eq!(game.play_turn(Command::Skip).events, vec![stuck, stuck]);
+    // commands that aren't moves don't take a turn
+    for command in [
+        Command::Undo,
+        Command::Redo,
+        Command::Hint,
+        Command::Save,
+        Command::Load,
+    ] {
+        assert!(!game.can_play(command));
+        assert!(game.play_turn(command).events.is_empty());
+    }
+    assert_eq!(
+        game.play_moves(&[Command::Undo, Command::Save]),
+        GameStatus::Continue
+    );
+    // and the Minotaurs don't move once Theseus escapes
+    let outcome = game.play_turn(Command::Right);
+    assert_eq!(
+        outcome.events,
+        vec![TurnEvent::Escaped {
+            from: Position::new(1, 3),
+            direction: Direction::Right
+        }]
+    );
+    assert_eq!(outcome.status, GameStatus::Win);
+}
+
+#[test]
+fn test_minotaur_brains() {
+    use theseus::{Brain, Command, Direction, Game, GameStatus, MinotaurBrain, Position};
+    let game = Game::from_board(
+        "XXXXX\n\
+         XM  X\n\
+         X   X\n\
+         XGT X\n\
+         XXXXX\n",
+    )
+    .unwrap();
+    let minotaur = game.minotaur_pos();
+    assert_eq!(game.brain().name(), "classic");
+    let choices: Vec<_> = Brain::ALL
+        .iter()
+        .map(|brain| brain.choose(&game, minotaur))
+        .collect();
+    assert_eq!(
+        choices,
+        vec![
+            Some(Direction::Right),
+            Some(Direction::Down),
+            Some(Direction::Down),
+            // the first of the shortest paths, trying directions in the order of Direction::ALL
+            Some(Direction::Down)
+        ]
+    );
+
+    // only the hunter finds its way around a wall
+    let board = "XXXXXXX\n\
+                 XM X TX\n\
+                 X  X  X\n\
+                 X    GX\n\
+                 XXXXXXX\n";
+    let skips = [Command::Skip; 4];
+    let mut game = Game::from_board(board).unwrap();
+    assert_eq!(game.play_moves(&skips), GameStatus::Continue);
+    assert_eq!(game.minotaur_pos(), Position::new(1, 2));
+    let mut game = Game::from_board(board).unwrap();
+    game.set_brain(Brain::Hunter);
+    assert_eq!(game.brain().name(), "hunter");
+    assert_eq!(game.play_moves(&skips), GameStatus::Lose);
+
+    // brains can be looked up by name, and written by hand
+    assert_eq!(Brain::by_name("vertical"), Some(Brain::VerticalFirst));
+    assert_eq!(Brain::by_name("sleepy"), None);
+    struct Sleepy;
+    impl MinotaurBrain for Sleepy {
+        fn name(&self) -> &str {
+            "sleepy"
+        }
+        fn choose(&self, _: &Game, _: Position) -> Option<Direction> {
+            None
+        }
+    }
+    let mut game = Game::from_board(board).unwrap();
+    game.set_brain(Sleepy);
+    game.play_moves(&skips);
+    assert_eq!(game.minotaur_pos(), Position::new(1, 1));
+    // and a game with any brain can still be moved to another thread
+    let minotaur = std::thread::spawn(move || game.minotaur_pos())
+        .join()
+        .unwrap();
+    assert_eq!(minotaur, Position::new(1, 1));
+
+    // the brain is kept in save files and can be set per level in a pack
+    let mut game = Game::from_board(board).unwrap();
+    game.set_brain(Brain::Greedy);
+    let loaded = Game::load_state(&game.save_state()).unwrap();
+    assert_eq!(loaded.brain().name(), "greedy");
+    let pack = format!("@level Hunted\n@brain hunter\n{}", board);
+    let levels = theseus::parse_pack(&pack).unwrap();
+    assert_eq!(levels[0].game.brain().name(), "hunter");
+    let pack = format!("@level Hunted\n@brain sleepy\n{}", board);
+    assert_eq!(
+        theseus::parse_pack(&pack).err().unwrap().kind,
+        theseus::PackErrorKind::UnknownBrain(String::from("sleepy"))
+    );
+}
+
+#[test]
+fn test_ruleset() {
+    use theseus::{Command, Direction, Game, GameStatus, Position, Ruleset, TurnEvent};
+    let board = "XXXXXXX\n\
+                 X  M TX\n\
+                 XXXXXGX\n\
+                 XXXXXXX\n";
+    let with_rules = |rules: Ruleset| {
+        let mut game = Game::from_board(board).unwrap();
+        game.set_rules(rules);
+        game
+    };
+
+    // the Minotaur can be faster or slower
+    let mut game = with_rules(Ruleset {
+        minotaur_steps: 1,
+        ..Ruleset::default()
+    });
+    assert_eq!(game.play_turn(Command::Skip).status, GameStatus::Continue);
+    assert_eq!(game.minotaur_pos(), Position::new(1, 4));
+    let mut game = with_rules(Ruleset::default());
+    assert_eq!(game.play_turn(Command::Skip).status, GameStatus::Lose);
+
+    // moving first, the Minotaur catches Theseus before he can reach the goal
+    let mut game = with_rules(Ruleset::default());
+    assert_eq!(game.play_turn(Command::Down).status, GameStatus::Win);
+    let mut game = with_rules(Ruleset {
+        minotaurs_first: true,
+        ..Ruleset::default()
+    });
+    let outcome = game.play_turn(Command::Down);
+    assert_eq!(outcome.status, GameStatus::Lose);
+    assert_eq!(
+        outcome.events.last(),
+        Some(&TurnEvent::Caught {
+            at: Position::new(1, 5)
+        })
+    );
+    // unless catches only count at the end of the turn, so Theseus gets away
+    let mut game = with_rules(Ruleset {
+        minotaurs_first: true,
+        catch_mid_turn: false,
+        ..Ruleset::default()
+    });
+    let outcome = game.play_turn(Command::Down);
+    assert_eq!(outcome.status, GameStatus::Win);
+    assert!(!outcome
+        .events
+        .iter()
+        .any(|event| matches!(event, TurnEvent::Caught { .. })));
+
+    // when Theseus has to move, skipping and walking into walls don't take a turn
+    let mut game = with_rules(Ruleset {
+        allow_skip: false,
+        ..Ruleset::default()
+    });
+    assert!(!game.can_play(Command::Skip));
+    assert!(!game.can_play(Command::Up));
+    assert!(game.can_play(Command::Left));
+    assert!(game.play_turn(Command::Skip).events.is_empty());
+    assert_eq!(
+        game.play_turn(Command::Up).events,
+        vec![TurnEvent::TheseusBlocked {
+            at: Position::new(1, 5),
+            direction: Direction::Up
+        }]
+    );
+    assert_eq!(game.minotaur_pos(), Position::new(1, 3));
+    assert_eq!(game.solve().unwrap(), vec![Command::Down]);
+
+    // a Minotaur kept off the goal treats it as a wall
+    let board = "XXXXXX\n\
+                 XMG TX\n\
+                 XXXXXX\n";
+    let mut game = Game::from_board(board).unwrap();
+    game.minotaur_move();
+    assert_eq!(game.minotaur_pos(), Position::new(1, 2));
+    let mut game = Game::from_board(board).unwrap();
+    let rules = Ruleset {
+        minotaurs_enter_goal: false,
+        ..Ruleset::default()
+    };
+    game.set_rules(rules);
+    assert_eq!(
+        game.minotaur_step(Position::new(1, 1), Direction::Right),
+        None
+    );
+    game.minotaur_move();
+    assert_eq!(game.minotaur_pos(), Position::new(1, 1));
+
+    // the rules are kept in save files
+    let loaded = Game::load_state(&game.save_state()).unwrap();
+    assert_eq!(loaded.rules(), rules);
+    let saved = Game::from_board(board).unwrap().save_state();
+    assert_eq!(
+        Game::load_state(&saved.replace("board:", "catch-at-end\nminotaur-steps 3\nboard:"))
+            .unwrap()
+            .rules(),
+        Ruleset {
+            minotaur_steps: 3,
+            catch_mid_turn: false,
+            ..Ruleset::default()
+        }
+    );
+}
+
+#[test]
+fn test_render() {
+    use theseus::{Game, Renderer, Theme};
+    // every kind of wall junction, plus a wall on its own
+    let game = Game::from_board(
+        "XXXXXXX\n\
+         XT X  X\n\
+         XXXXX X\n\
+         X  M XX\n\
+         XXX XGX\n\
+         X X   X\n\
+         XXXXXXX\n",
+    )
+    .unwrap();
+    assert_eq!(
+        game.to_string(),
+        "╔══╦══╗\n\
+         ║T ║  ║\n\
+         ╠══╩═ ║\n\
+         ║  M ═╣\n\
+         ╠═╗ ◻G║\n\
+         ║ ║   ║\n\
+         ╚═╩═══╝\n"
+    );
+    // a four-way junction, and walls that stop at the end of a short row
+    let game = Game::from_board("XXXXX\nX X X\nXXXXX\nXTXMX\nXXXXX\n X G\n").unwrap();
+    assert_eq!(
+        game.to_string(),
+        "╔═╦═╗\n║ ║ ║\n╠═╬═╣\n║T║M║\n╚╦╩═╝\n ║ G\n"
+    );
+    // thin-wall boards are drawn at twice the size, with their exits marked
+    let game = Game::parse("+-+-+\n|T M|\n+ +-+\n|G  E\n+-+-+\n").unwrap();
+    assert_eq!(game.to_string(), "╔═══╗\n║T M║\n║ ══╝\n║G  E\n╚════\n");
+
+    // colors wrap each glyph, and writing gives the same text
+    let theme = Theme {
+        theseus: Some("1"),
+        minotaur: None,
+        goal: None,
+        wall: None,
+    };
+    let game = Game::from_board("XXXXX\nXTMGX\nXXXXX\n").unwrap();
+    assert_eq!(game.render(&theme), "╔═══╗\n║\x1b[1mT\x1b[0mMG║\n╚═══╝\n");
+    let mut out = Vec::new();
+    game.render_to(&mut out, &theme).unwrap();
+    assert_eq!(String::from_utf8(out).unwrap(), game.render(&theme));
+
+    // without ANSI escapes, frames are plain boards followed by the move count and the message
+    let mut renderer = Renderer::new(false, theme);
+    renderer.set_moves(3);
+    let mut out = Vec::new();
+    renderer.draw(&mut out, &game, "You win!").unwrap();
+    assert_eq!(
+        String::from_utf8(out).unwrap(),
+        "╔═══╗\n║TMG║\n╚═══╝\nMoves: 3\nYou win!\n"
+    );
+}
+
+#[test]
+fn test_to_board() {
+    use theseus::{Command, Game, GameStatus};
+    // boards written in the usual way come back out unchanged
+    let board = "XXXXXXX\n\
+                 XT   GX\n\
+                 X XX XX\n\
+                 XM    E\n\
+                 XXXXXXX\n";
+    let game = Game::from_board(board).unwrap();
+    assert_eq!(game.to_board().as_deref(), Some(board));
+    assert_eq!(Game::from_board(&game.to_board().unwrap()).unwrap(), game);
+    // thin-wall boards are written in their own format, which `from_board` reads back too
+    let thin = "+-+-+-+\n|T   G|\n+ +-+ +\n|M    E\n+-+-+-+\n";
+    let mut thin_game = Game::parse(thin).unwrap();
+    assert_eq!(thin_game.to_board().as_deref(), Some(thin));
+    thin_game.play_turn(Command::Right);
+    let written = thin_game.to_board().unwrap();
+    assert_eq!(written, "+-+-+-+\n|  T G|\n+ +-+ +\n|  M  E\n+-+-+-+\n");
+    assert_eq!(Game::from_board(&written).unwrap(), thin_game);
+
+    // pieces are written where they've moved to
+    let mut moved = game.clone();
+    moved.play_turn(Command::Right);
+    assert_eq!(
+        moved.to_board().as_deref(),
+        Some(
+            "XXXXXXX\n\
+             X T  GX\n\
+             X XX XX\n\
+             X M   E\n\
+             XXXXXXX\n"
+        )
+    );
+
+    // states the board text can't hold aren't written, but still save
+    let mut on_goal = Game::from_board("XXXXXXX\nXM G TX\nXXXXXXX\n").unwrap();
+    on_goal.play_turn(Command::Skip);
+    assert!(on_goal.is_minotaur(1, 3) && on_goal.is_goal(1, 3));
+    assert_eq!(on_goal.to_board(), None);
+    assert_eq!(Game::load_state(&on_goal.save_state()).unwrap(), on_goal);
+    let mut hunted = game.clone();
+    hunted.set_brain(theseus::Brain::Hunter);
+    assert_eq!(hunted.to_board(), None);
+
+    // every shipped board, and every state on the way to solving it, round trips
+    let mut boards: Vec<String> = [
+        include_str!("../board0.txt"),
+        include_str!("../board1.txt"),
+        include_str!("../board2.txt"),
+        include_str!("../board3.txt"),
+    ]
+    .map(String::from)
+    .into();
+    let levels = theseus::parse_pack(include_str!("../levels.pack")).unwrap();
+    boards.extend(levels.into_iter().map(|level| level.board));
+    boards.push(theseus::generate(&theseus::GeneratorOptions::new(9, 9)).unwrap());
+    for board in boards {
+        let mut game = Game::parse(&board).unwrap();
+        let solution = game.solve().unwrap_or_default();
+        for command in solution {
+            match game.to_board() {
+                Some(written) => assert_eq!(Game::from_board(&written).unwrap(), game),
+                // which for these boards only happens when a Minotaur is on the goal
+                None => assert!(game
+                    .minotaur_positions()
+                    .iter()
+                    .any(|minotaur| game.is_goal(minotaur.row, minotaur.col))),
+            }
+            if game.play_turn(command).status != GameStatus::Continue {
+                break;
+            }
+        }
+    }
+}